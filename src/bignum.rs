@@ -0,0 +1,380 @@
+// FizzBuzz con números arbitrariamente grandes.
+// =============================================================
+// `fizzbuzz(n: u32)` no pasa de unos 4 mil millones y `u64` de unos 1.8·10^19.
+// `BigUint` es un entero sin signo de tamaño arbitrario escrito a mano: se guarda
+// como una lista de "dígitos" en base 2^32 (`limbs`), del menos al más significativo.
+//
+// Para saber si `n` es divisible por `d` no hace falta dividir del todo: basta con
+// el resto, que se calcula recorriendo los dígitos de mayor a menor y reduciendo
+// módulo `d` en cada paso (`resto = (resto * 2^32 + dígito) % d`).
+
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::str::FromStr;
+
+use crate::DivisionByZero;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    // Sin ceros a la izquierda: el `0` es la lista vacía.
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError(pub String);
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.parse_biguint", self.0))
+    }
+}
+
+impl Error for ParseBigUintError {}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // Quita los ceros a la izquierda tras una operación.
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    // `self = self * factor + addend`, el paso básico para leer un número en decimal.
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry = u64::from(addend);
+        for limb in &mut self.limbs {
+            let value = u64::from(*limb) * u64::from(factor) + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        self.normalize();
+    }
+
+    // Divide por `divisor` en el sitio y devuelve el resto.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = (rem << 32) | u64::from(*limb);
+            *limb = (value / u64::from(divisor)) as u32;
+            rem = value % u64::from(divisor);
+        }
+        self.normalize();
+        rem as u32
+    }
+
+    // Suma `1` en el sitio.
+    pub fn increment(&mut self) {
+        for limb in &mut self.limbs {
+            let (value, overflow) = limb.overflowing_add(1);
+            *limb = value;
+            if !overflow {
+                return;
+            }
+        }
+        self.limbs.push(1);
+    }
+
+    // `self` elevado a `exp`, por exponenciación binaria.
+    pub fn pow(&self, mut exp: u32) -> BigUint {
+        let mut result = BigUint::from(1u64);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // El resto de dividir por `divisor`, reduciendo módulo `divisor` dígito a dígito.
+    pub fn checked_rem(&self, divisor: u64) -> Result<u64, DivisionByZero> {
+        if divisor == 0 {
+            return Err(DivisionByZero);
+        }
+
+        let divisor = u128::from(divisor);
+        let rem = self.limbs.iter().rev().fold(0u128, |rem, &limb| {
+            ((rem << 32) | u128::from(limb)) % divisor
+        });
+        Ok(rem as u64)
+    }
+
+    // Como `Divisible::checked_is_divisible_by`: dividir por `0` es un error.
+    pub fn checked_is_divisible_by(&self, divisor: u64) -> Result<bool, DivisionByZero> {
+        self.checked_rem(divisor).map(|rem| rem == 0)
+    }
+
+    // Como `Divisible::is_divisible_by`: nada es divisible por `0`.
+    pub fn is_divisible_by(&self, divisor: u64) -> bool {
+        self.checked_is_divisible_by(divisor).unwrap_or(false)
+    }
+
+    // El valor como `u64`, si cabe.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(u64::from(low)),
+            [low, high] => Some(u64::from(high) << 32 | u64::from(low)),
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> BigUint {
+        let mut big = BigUint {
+            limbs: vec![n as u32, (n >> 32) as u32],
+        };
+        big.normalize();
+        big
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    // Solo dígitos decimales (se admiten `_` como separadores, como en Rust: `1_000`).
+    fn from_str(s: &str) -> Result<BigUint, ParseBigUintError> {
+        let error = || ParseBigUintError(s.to_owned());
+        if !s.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let mut big = BigUint::zero();
+        for c in s.chars().filter(|&c| c != '_') {
+            let digit = c.to_digit(10).ok_or_else(error)?;
+            big.mul_add_small(10, digit);
+        }
+        Ok(big)
+    }
+}
+
+impl fmt::Display for BigUint {
+    // Se extraen los dígitos de 9 en 9 dividiendo por 10^9.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        loop {
+            chunks.push(rest.div_rem_small(CHUNK));
+            if rest.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap_or(0).to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:09}", chunk));
+        }
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+// Multiplicación "de colegio", dígito a dígito.
+impl std::ops::Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let value = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u32;
+        }
+
+        let mut product = BigUint { limbs };
+        product.normalize();
+        product
+    }
+}
+
+// Como `Token`, pero con números de cualquier tamaño.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BigToken {
+    Fizz,
+    Buzz,
+    FizzBuzz,
+    Number(BigUint),
+}
+
+impl BigToken {
+    pub fn of(n: &BigUint) -> BigToken {
+        match (n.is_divisible_by(3), n.is_divisible_by(5)) {
+            (true, true) => BigToken::FizzBuzz,
+            (true, false) => BigToken::Fizz,
+            (false, true) => BigToken::Buzz,
+            (false, false) => BigToken::Number(n.clone()),
+        }
+    }
+}
+
+impl fmt::Display for BigToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BigToken::Fizz => f.write_str("fizz"),
+            BigToken::Buzz => f.write_str("buzz"),
+            BigToken::FizzBuzz => f.write_str("fizzbuzz"),
+            BigToken::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+// Iterador infinito de FizzBuzz a partir de cualquier número, p.ej.:
+// `BigFizzBuzz::new(BigUint::from(10).pow(40)).take(100)`
+#[derive(Debug, Clone)]
+pub struct BigFizzBuzz {
+    next: BigUint,
+}
+
+impl BigFizzBuzz {
+    pub fn new(start: BigUint) -> BigFizzBuzz {
+        BigFizzBuzz { next: start }
+    }
+
+    // Atajo para empezar en un número escrito en decimal.
+    pub fn from_decimal(start: &str) -> Result<BigFizzBuzz, ParseBigUintError> {
+        start.parse().map(BigFizzBuzz::new)
+    }
+}
+
+impl Iterator for BigFizzBuzz {
+    type Item = BigToken;
+
+    fn next(&mut self) -> Option<BigToken> {
+        let token = BigToken::of(&self.next);
+        self.next.increment();
+        Some(token)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+// Nunca se termina.
+impl FusedIterator for BigFizzBuzz {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigUint {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for s in [
+            "0",
+            "4294967295",
+            "4294967296",
+            "18446744073709551615",
+            "18446744073709551616",
+            "10000000000000000000000000000000000000000",
+            "340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("1_000_000").to_u64(), Some(1_000_000));
+        assert_eq!(big("18446744073709551616").to_u64(), None);
+        assert_eq!(format!("{:>5}", BigUint::from(42)), "   42");
+    }
+
+    #[test]
+    fn pow_and_increment_cross_limbs() {
+        assert_eq!(BigUint::from(2).pow(64), big("18446744073709551616"));
+        assert_eq!(
+            BigUint::from(10).pow(40).to_string(),
+            format!("1{}", "0".repeat(40))
+        );
+        assert_eq!(BigUint::from(7).pow(0), BigUint::from(1));
+
+        let mut n = BigUint::from(u64::MAX);
+        n.increment();
+        assert_eq!(n, BigUint::from(2).pow(64));
+        let mut zero = BigUint::zero();
+        zero.increment();
+        assert_eq!(zero, BigUint::from(1));
+    }
+
+    #[test]
+    fn checked_rem_matches_u128() {
+        let values = [
+            0,
+            1,
+            u128::from(u64::MAX),
+            u128::MAX,
+            10u128.pow(38) + 12_345,
+        ];
+        let divisors = [1, 2, 3, 7, 1_000_000_007, u64::MAX];
+        for n in values {
+            let big = big(&n.to_string());
+            for d in divisors {
+                assert_eq!(
+                    big.checked_rem(d),
+                    Ok((n % u128::from(d)) as u64),
+                    "{n} % {d}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn divisor_zero_is_an_error() {
+        let n = BigUint::from(15);
+        assert_eq!(n.checked_rem(0), Err(DivisionByZero));
+        assert_eq!(n.checked_is_divisible_by(0), Err(DivisionByZero));
+        assert!(!n.is_divisible_by(0));
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for s in ["", "_1", "-1", "+1", "12a", "1.5", " 1"] {
+            assert_eq!(s.parse::<BigUint>(), Err(ParseBigUintError(s.to_owned())));
+        }
+    }
+
+    #[test]
+    fn fizzbuzz_from_ten_to_the_forty() {
+        let start = BigUint::from(10).pow(40);
+        let plus = |k: u64| {
+            let mut n = start.clone();
+            (0..k).for_each(|_| n.increment());
+            BigToken::Number(n)
+        };
+        let tokens: Vec<BigToken> = BigFizzBuzz::new(start.clone()).take(6).collect();
+        assert_eq!(
+            tokens,
+            [
+                BigToken::Buzz,
+                plus(1),
+                BigToken::Fizz,
+                plus(3),
+                plus(4),
+                BigToken::FizzBuzz,
+            ]
+        );
+        assert_eq!(tokens[1].to_string(), format!("1{}1", "0".repeat(39)));
+    }
+}
@@ -0,0 +1,133 @@
+// Divisibilidad sin división.
+// =============================================================
+// `n % d` es una de las instrucciones más lentas del procesador. Cuando el mismo
+// divisor se usa millones de veces (como 3 y 5 en `fizzbuzz_to`), se puede calcular
+// una vez su inverso módulo 2^64 y comprobar la divisibilidad con una multiplicación
+// y una comparación (Granlund–Montgomery, "Division by invariant integers using
+// multiplication", 1994):
+// • si `d` es impar, tiene inverso `d⁻¹` módulo 2^64 y la multiplicación por `d⁻¹`
+//   es una biyección que lleva los múltiplos de `d` (`k * d`, con `k <= MAX / d`)
+//   justo a `0..=MAX / d`; así que `d | n` si y solo si `n * d⁻¹ <= MAX / d`;
+// • si `d = d' * 2^k` con `d'` impar, además los `k` bits bajos de `n` deben ser `0`:
+//   rotando `n * d'⁻¹` `k` bits a la derecha, esos bits pasan a ser los altos y la
+//   misma comparación comprueba las dos cosas a la vez.
+
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Divisor {
+    divisor: u64,
+    // Inverso de la parte impar del divisor, módulo 2^64.
+    inverse: u64,
+    // Ceros finales del divisor (la potencia de 2).
+    shift: u32,
+    // `u64::MAX / divisor`: los múltiplos de `divisor`, tras multiplicar y rotar,
+    // quedan en `0..=limit`.
+    limit: u64,
+}
+
+impl Divisor {
+    // Precalcula el inverso. Como `is_divisible_by`, `0` no divide a ningún número.
+    pub const fn new(divisor: u64) -> Divisor {
+        if divisor == 0 {
+            return Divisor {
+                divisor,
+                inverse: 0,
+                shift: 0,
+                limit: 0,
+            };
+        }
+
+        let shift = divisor.trailing_zeros();
+        let odd = divisor >> shift;
+
+        // Método de Newton: si `odd * x ≡ 1 (mod 2^b)`, entonces `x * (2 - odd * x)` es
+        // el inverso módulo 2^(2b). `x = odd` ya es correcto módulo 2^3 (en 8 todo
+        // impar es su propio inverso): 3 → 6 → 12 → 24 → 48 → 96 bits.
+        let mut inverse = odd;
+        let mut i = 0;
+        while i < 5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(odd.wrapping_mul(inverse)));
+            i += 1;
+        }
+
+        Divisor {
+            divisor,
+            inverse,
+            shift,
+            limit: u64::MAX / divisor,
+        }
+    }
+
+    pub const fn get(self) -> u64 {
+        self.divisor
+    }
+
+    // ¿Divide a `n`? Sin ninguna división.
+    #[inline]
+    pub const fn divides(self, n: u64) -> bool {
+        if self.divisor == 0 {
+            return false;
+        }
+        n.wrapping_mul(self.inverse).rotate_right(self.shift) <= self.limit
+    }
+}
+
+impl From<u64> for Divisor {
+    fn from(divisor: u64) -> Divisor {
+        Divisor::new(divisor)
+    }
+}
+
+// Solo interesa el divisor, no los valores precalculados.
+impl fmt::Debug for Divisor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Divisor").field(&self.divisor).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rng;
+
+    fn check(divisor: u64, n: u64) {
+        // El resto de la división de verdad (`None` al dividir por `0`).
+        let expected = n.checked_rem(divisor) == Some(0);
+        assert_eq!(
+            Divisor::new(divisor).divides(n),
+            expected,
+            "{divisor} | {n}"
+        );
+    }
+
+    #[test]
+    fn matches_remainder() {
+        let mut rng = Rng::new(15);
+        let mut divisors: Vec<u64> = (0..=300).collect();
+        divisors.extend((0..64).map(|k| 1u64 << k));
+        divisors.extend([3 << 40, 5 << 62, 1_000_000_007, u64::MAX, u64::MAX - 1]);
+        divisors.extend((0..200).map(|_| rng.next_u64() >> rng.gen_range(0..64)));
+
+        for &divisor in &divisors {
+            for n in (0..=1_000).chain([u64::MAX, u64::MAX - 1, 1 << 63]) {
+                check(divisor, n);
+            }
+            // Múltiplos y sus vecinos, donde un error en el límite se notaría.
+            for _ in 0..200 {
+                let multiple = divisor.wrapping_mul(rng.next_u64() % (u64::MAX / divisor.max(1)));
+                for n in [multiple.wrapping_sub(1), multiple, multiple.wrapping_add(1)] {
+                    check(divisor, n);
+                }
+                check(divisor, rng.next_u64());
+            }
+        }
+    }
+
+    #[test]
+    fn zero_divides_nothing() {
+        assert!(!Divisor::new(0).divides(0));
+        assert!(!Divisor::from(0).divides(u64::MAX));
+        assert_eq!(Divisor::new(42).get(), 42);
+    }
+}
@@ -0,0 +1,460 @@
+// Lenguaje de reglas de FizzBuzz.
+// =============================================================
+// Permite describir un `FizzBuzzRules` como texto (p.ej. en un fichero) sin
+// recompilar:
+//
+//     3=Fizz, 5=Buzz, 7=Bazz; fallback=number; sep=""
+//
+// • Las sentencias se separan con `;` o con saltos de línea, y dentro de una
+//   sentencia los elementos se separan con `,` (tras una `,` se puede saltar de línea).
+// • `<divisor>=<palabra>` añade una regla de divisibilidad, en el orden en el que aparece.
+// • `prime`, `square`, `palindrome` y `digit0` ... `digit9` son reglas con predicado:
+//   `prime=Prime` escribe "Prime" para los números primos.
+// • `fallback=number` (por defecto), `fallback=empty` o `fallback="texto"` indican
+//   qué escribir cuando ninguna regla coincide.
+// • `sep="texto"` es el separador entre palabras concatenadas (por defecto, ninguno).
+// • `numbers=roman` indica cómo se escriben los números (ver `NumberFormat`).
+// • Las palabras pueden ir sin comillas (`Fizz`) o entre comillas dobles
+//   (`"Fizz Buzz!"`, con los escapes `\"`, `\\`, `\n` y `\t`).
+// • `#` inicia un comentario hasta el final de la línea.
+//
+// Los errores indican la línea y la columna (contando desde 1) donde se detectan.
+
+use std::error::Error;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+use crate::rules::{contains_digit, is_palindrome, is_perfect_square};
+use crate::{is_prime, Fallback, FizzBuzzRules, NumberFormat, Rule, UnknownNumberFormat};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!(
+            "dsl.error",
+            self.line,
+            self.column,
+            self.message
+        ))
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Number(u64),
+    Ident(String),
+    Str(String),
+    Equals,
+    Comma,
+    Semicolon,
+    Newline,
+    End,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Kind::Number(n) => f.write_str(&crate::message!("dsl.token.number", n)),
+            Kind::Ident(ident) => write!(f, "`{}`", ident),
+            Kind::Str(text) => {
+                f.write_str(&crate::message!("dsl.token.text", format!("{:?}", text)))
+            }
+            Kind::Equals => f.write_str("`=`"),
+            Kind::Comma => f.write_str("`,`"),
+            Kind::Semicolon => f.write_str("`;`"),
+            Kind::Newline => f.write_str(&crate::message!("dsl.token.newline")),
+            Kind::End => f.write_str(&crate::message!("dsl.token.end")),
+        }
+    }
+}
+
+struct Token {
+    kind: Kind,
+    line: usize,
+    column: usize,
+}
+
+// Analizador léxico: convierte el texto en `Token`s recordando su posición.
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Lexer<'a> {
+        Lexer {
+            chars: src.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn error(&self, line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        // Espacios y comentarios (pero no los saltos de línea, que separan sentencias).
+        while let Some(&c) = self.chars.peek() {
+            if c == '#' {
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.bump();
+                }
+            } else if c.is_whitespace() && c != '\n' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        let (line, column) = (self.line, self.column);
+        let token = |kind| Token { kind, line, column };
+
+        let c = match self.bump() {
+            None => return Ok(token(Kind::End)),
+            Some(c) => c,
+        };
+
+        match c {
+            '=' => Ok(token(Kind::Equals)),
+            ',' => Ok(token(Kind::Comma)),
+            ';' => Ok(token(Kind::Semicolon)),
+            '\n' => Ok(token(Kind::Newline)),
+            '"' => self.string(line, column).map(|text| token(Kind::Str(text))),
+            c if c.is_ascii_digit() => {
+                let mut digits = c.to_string();
+                while let Some(&c) = self.chars.peek().filter(|c| c.is_ascii_digit()) {
+                    digits.push(c);
+                    self.bump();
+                }
+                digits
+                    .parse()
+                    .map(|n| token(Kind::Number(n)))
+                    .map_err(|_| self.error(line, column, crate::message!("dsl.number_too_big")))
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(&c) = self
+                    .chars
+                    .peek()
+                    .filter(|&&c| c.is_alphanumeric() || c == '_' || c == '-')
+                {
+                    ident.push(c);
+                    self.bump();
+                }
+                Ok(token(Kind::Ident(ident)))
+            }
+            c => Err(self.error(line, column, crate::message!("dsl.unexpected_char", c))),
+        }
+    }
+
+    // Lee el resto de una cadena entre comillas (la `"` inicial ya se ha consumido).
+    fn string(&mut self, line: usize, column: usize) -> Result<String, ParseError> {
+        let mut text = String::new();
+        loop {
+            let (escape_line, escape_column) = (self.line, self.column);
+            match self.bump() {
+                None | Some('\n') => {
+                    return Err(self.error(
+                        line,
+                        column,
+                        crate::message!("dsl.unterminated_string"),
+                    ))
+                }
+                Some('"') => return Ok(text),
+                Some('\\') => match self.bump() {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some(c) => {
+                        return Err(self.error(
+                            escape_line,
+                            escape_column,
+                            crate::message!("dsl.unknown_escape", c),
+                        ))
+                    }
+                    None => {
+                        return Err(self.error(
+                            line,
+                            column,
+                            crate::message!("dsl.unterminated_string"),
+                        ))
+                    }
+                },
+                Some(c) => text.push(c),
+            }
+        }
+    }
+}
+
+// Analizador sintáctico: recorre los `Token`s y va construyendo las reglas.
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    current: Token,
+    rules: FizzBuzzRules,
+    separator: String,
+    fallback: Fallback,
+    number_format: NumberFormat,
+}
+
+impl<'a> Parser<'a> {
+    fn new(src: &'a str) -> Result<Parser<'a>, ParseError> {
+        let mut lexer = Lexer::new(src);
+        let current = lexer.next_token()?;
+
+        Ok(Parser {
+            lexer,
+            current,
+            rules: FizzBuzzRules::new(),
+            separator: String::new(),
+            fallback: Fallback::Number,
+            number_format: NumberFormat::Decimal,
+        })
+    }
+
+    // Devuelve el token actual y lee el siguiente.
+    fn advance(&mut self) -> Result<Token, ParseError> {
+        let next = self.lexer.next_token()?;
+        Ok(std::mem::replace(&mut self.current, next))
+    }
+
+    fn unexpected(token: &Token, expected: &str) -> ParseError {
+        ParseError {
+            line: token.line,
+            column: token.column,
+            message: crate::message!("dsl.expected", expected, token.kind),
+        }
+    }
+
+    fn parse(mut self) -> Result<FizzBuzzRules, ParseError> {
+        loop {
+            // Sentencias vacías: `;;` o líneas en blanco.
+            while matches!(self.current.kind, Kind::Newline | Kind::Semicolon) {
+                self.advance()?;
+            }
+            if self.current.kind == Kind::End {
+                break;
+            }
+
+            self.item()?;
+            loop {
+                match self.current.kind {
+                    Kind::Comma => {
+                        self.advance()?;
+                        while self.current.kind == Kind::Newline {
+                            self.advance()?;
+                        }
+                        self.item()?;
+                    }
+                    Kind::Newline | Kind::Semicolon | Kind::End => break,
+                    _ => {
+                        return Err(Parser::unexpected(
+                            &self.current,
+                            &crate::message!("dsl.expected.separator"),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(self
+            .rules
+            .with_separator(self.separator)
+            .with_fallback(self.fallback)
+            .with_number_format(self.number_format))
+    }
+
+    // `<clave>=<valor>`
+    fn item(&mut self) -> Result<(), ParseError> {
+        let key = self.advance()?;
+        let equals = self.advance()?;
+        if equals.kind != Kind::Equals {
+            return Err(Parser::unexpected(&equals, "`=`"));
+        }
+        let value = self.advance()?;
+
+        match key.kind {
+            Kind::Number(0) => Err(ParseError {
+                line: key.line,
+                column: key.column,
+                message: crate::message!("dsl.zero_divisor"),
+            }),
+            Kind::Number(divisor) => {
+                let word = Parser::word(value)?;
+                self.rules.push(divisor, word);
+                Ok(())
+            }
+            Kind::Ident(ref name) => match name.as_str() {
+                "fallback" => {
+                    self.fallback = match value.kind {
+                        Kind::Ident(ref kind) if kind == "number" => Fallback::Number,
+                        Kind::Ident(ref kind) if kind == "empty" => Fallback::Text(String::new()),
+                        Kind::Str(text) => Fallback::Text(text),
+                        _ => {
+                            return Err(Parser::unexpected(
+                                &value,
+                                &crate::message!("dsl.expected.fallback"),
+                            ))
+                        }
+                    };
+                    Ok(())
+                }
+                "sep" => {
+                    self.separator = Parser::word(value)?;
+                    Ok(())
+                }
+                "numbers" => {
+                    let (line, column) = (value.line, value.column);
+                    self.number_format =
+                        Parser::word(value)?
+                            .parse()
+                            .map_err(|err: UnknownNumberFormat| ParseError {
+                                line,
+                                column,
+                                message: err.to_string(),
+                            })?;
+                    Ok(())
+                }
+                _ => {
+                    let rule = Parser::predicate_rule(&key, name, Parser::word(value)?)?;
+                    self.rules.push_rule(rule);
+                    Ok(())
+                }
+            },
+            _ => Err(Parser::unexpected(
+                &key,
+                &crate::message!("dsl.expected.key"),
+            )),
+        }
+    }
+
+    // Las reglas con predicado que se pueden nombrar en el texto.
+    fn predicate_rule(key: &Token, name: &str, word: String) -> Result<Rule, ParseError> {
+        let rule = match name {
+            "prime" => Rule::when(is_prime, word),
+            "square" => Rule::when(is_perfect_square, word),
+            "palindrome" => Rule::when(is_palindrome, word),
+            _ => match name
+                .strip_prefix("digit")
+                .and_then(|d| d.parse::<u8>().ok())
+            {
+                Some(digit) if digit < 10 => Rule::when(move |n| contains_digit(n, digit), word),
+                _ => {
+                    return Err(ParseError {
+                        line: key.line,
+                        column: key.column,
+                        message: crate::message!("dsl.unknown_key", name),
+                    })
+                }
+            },
+        };
+        Ok(rule)
+    }
+
+    // Una palabra: sin comillas, entre comillas o incluso un número.
+    fn word(token: Token) -> Result<String, ParseError> {
+        match token.kind {
+            Kind::Ident(word) | Kind::Str(word) => Ok(word),
+            Kind::Number(n) => Ok(n.to_string()),
+            _ => Err(Parser::unexpected(
+                &token,
+                &crate::message!("dsl.expected.word"),
+            )),
+        }
+    }
+}
+
+// Construye las reglas a partir de su descripción en texto.
+pub fn parse_rules(src: &str) -> Result<FizzBuzzRules, ParseError> {
+    Parser::new(src)?.parse()
+}
+
+// Así también se puede escribir `"3=Fizz, 5=Buzz".parse::<FizzBuzzRules>()`.
+impl FromStr for FizzBuzzRules {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<FizzBuzzRules, ParseError> {
+        parse_rules(src)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(rules: &FizzBuzzRules, range: std::ops::RangeInclusive<u64>) -> Vec<String> {
+        range.map(|n| rules.token(n).into_owned()).collect()
+    }
+
+    // Línea y columna del error al analizar `src`.
+    fn error_at(src: &str) -> (usize, usize) {
+        let err = parse_rules(src).expect_err(src);
+        (err.line, err.column)
+    }
+
+    #[test]
+    fn parses_rules() {
+        let rules = parse_rules("3=Fizz, 5=Buzz, 7=Bazz; fallback=number; sep=\"\"").unwrap();
+        let expected = FizzBuzzRules::new()
+            .rule(3, "Fizz")
+            .rule(5, "Buzz")
+            .rule(7, "Bazz");
+        assert_eq!(tokens(&rules, 1..=120), tokens(&expected, 1..=120));
+    }
+
+    #[test]
+    fn parses_options_comments_and_escapes() {
+        let src = "# reglas\n3 = fizz,\n  5 = \"b\\\"uzz\"\n\nsep=\"-\" ; fallback=\"\\t\"\nnumbers=roman";
+        let rules = parse_rules(src).unwrap();
+        assert_eq!(rules.token(15), "fizz-b\"uzz");
+        assert_eq!(rules.token(2), "\t");
+        assert_eq!(rules.number_format(), NumberFormat::Roman);
+
+        let rules: FizzBuzzRules = "prime=p, digit7=seven; fallback=empty".parse().unwrap();
+        assert_eq!(rules.token(7), "pseven");
+        assert_eq!(rules.token(17), "pseven");
+        assert_eq!(rules.token(8), "");
+    }
+
+    #[test]
+    fn error_positions() {
+        assert_eq!(error_at("3=Fizz, 5 Buzz"), (1, 11));
+        assert_eq!(error_at("3=Fizz\n0=Zero"), (2, 1));
+        assert_eq!(error_at("3=Fizz\n  5=\"Buzz"), (2, 5));
+        assert_eq!(error_at("3=\"a\\qb\""), (1, 5));
+        assert_eq!(error_at("3=Fizz; 5=Buzz 7=Bazz"), (1, 16));
+        assert_eq!(error_at("3=Fizz\n\n  @"), (3, 3));
+        assert_eq!(error_at("cube=Cube"), (1, 1));
+        assert_eq!(error_at("digit12=x"), (1, 1));
+        assert_eq!(error_at("3=Fizz\nnumbers=klingon"), (2, 9));
+        assert_eq!(error_at("99999999999999999999=big"), (1, 1));
+        assert_eq!(error_at("fallback=,"), (1, 10));
+        assert_eq!(error_at("3="), (1, 3));
+    }
+}
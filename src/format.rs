@@ -0,0 +1,319 @@
+// Formatos de salida estructurados.
+// =============================================================
+// Además del texto plano de `fizzbuzz_to` (una palabra por línea), cada número se
+// puede escribir como un registro `{"n":15,"token":"fizzbuzz","matched":[3,5]}`,
+// donde `matched` son los divisores de las reglas de divisibilidad que coinciden
+// y cuya palabra llega al token: las reglas con cierre no tienen divisor, y las que
+// sustituye una regla `RuleMode::Override` posterior no aparecen. Los formatos son:
+// • `json`: un único array JSON con todos los registros;
+// • `ndjson`: un objeto JSON por línea;
+// • `csv`: una cabecera `n,token,matched` y una fila por número, con los divisores
+//   separados por `;`.
+// Los codificadores están escritos a mano, sin dependencias externas.
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::{FizzBuzzRules, RuleMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputFormat {
+    // Texto plano, igual que `fizzbuzz_to`.
+    #[default]
+    Text,
+    Json,
+    Ndjson,
+    Csv,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFormat(pub String);
+
+impl fmt::Display for UnknownFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.unknown_format", self.0))
+    }
+}
+
+impl Error for UnknownFormat {}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<OutputFormat, UnknownFormat> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "csv" => Ok(OutputFormat::Csv),
+            other => Err(UnknownFormat(other.to_owned())),
+        }
+    }
+}
+
+// Lo que se sabe de un número: su token y qué divisores han coincidido.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub n: u64,
+    pub token: Cow<'a, str>,
+    pub matched: Vec<u64>,
+}
+
+impl FizzBuzzRules {
+    pub fn record(&self, n: u64) -> Record<'_> {
+        // Igual que al combinar las palabras: una regla `Override` que coincide
+        // descarta las anteriores.
+        let mut matched = Vec::new();
+        for rule in self.rules().iter().filter(|rule| rule.word(n).is_some()) {
+            if rule.mode() == RuleMode::Override {
+                matched.clear();
+            }
+            matched.extend(rule.divisor());
+        }
+
+        Record {
+            n,
+            token: self.token(n),
+            matched,
+        }
+    }
+
+    // Como `write_fizzbuzz_to`, pero en el formato indicado.
+    pub fn write_formatted_to<W: Write>(
+        &self,
+        out: &mut W,
+        n: u64,
+        format: OutputFormat,
+    ) -> io::Result<()> {
+        let mut writer = RecordWriter::new(out, format);
+        for n in 1..=n {
+            writer.write(&self.record(n))?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+// Escribe registros uno a uno (sin guardarlos en memoria) en el formato elegido.
+// Hay que llamar a `finish` al terminar para cerrar el array JSON.
+pub struct RecordWriter<W: Write> {
+    out: W,
+    format: OutputFormat,
+    records: u64,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(out: W, format: OutputFormat) -> RecordWriter<W> {
+        RecordWriter {
+            out,
+            format,
+            records: 0,
+        }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let out = &mut self.out;
+        match self.format {
+            OutputFormat::Text => writeln!(out, "{}", record.token)?,
+            OutputFormat::Json => {
+                out.write_all(if self.records == 0 { b"[\n" } else { b",\n" })?;
+                write_json_object(out, record)?;
+            }
+            OutputFormat::Ndjson => {
+                write_json_object(out, record)?;
+                out.write_all(b"\n")?;
+            }
+            OutputFormat::Csv => {
+                if self.records == 0 {
+                    out.write_all(b"n,token,matched\n")?;
+                }
+                write!(out, "{},", record.n)?;
+                write_csv_field(out, &record.token)?;
+                out.write_all(b",")?;
+                write_joined(out, &record.matched, ";")?;
+                out.write_all(b"\n")?;
+            }
+        }
+
+        self.records += 1;
+        Ok(())
+    }
+
+    // Cierra la salida (el `]` de JSON o la cabecera CSV si no hubo filas) y
+    // devuelve el `Write` subyacente.
+    pub fn finish(mut self) -> io::Result<W> {
+        match (self.format, self.records) {
+            (OutputFormat::Json, 0) => self.out.write_all(b"[]\n")?,
+            (OutputFormat::Json, _) => self.out.write_all(b"\n]\n")?,
+            (OutputFormat::Csv, 0) => self.out.write_all(b"n,token,matched\n")?,
+            _ => {}
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+fn write_joined<W: Write>(out: &mut W, values: &[u64], separator: &str) -> io::Result<()> {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            out.write_all(separator.as_bytes())?;
+        }
+        write!(out, "{}", value)?;
+    }
+    Ok(())
+}
+
+fn write_json_object<W: Write>(out: &mut W, record: &Record) -> io::Result<()> {
+    write!(out, "{{\"n\":{},\"token\":", record.n)?;
+    write_json_string(out, &record.token)?;
+    out.write_all(b",\"matched\":[")?;
+    write_joined(out, &record.matched, ",")?;
+    out.write_all(b"]}")
+}
+
+// Una cadena JSON con sus escapes (RFC 8259): comillas, barra invertida y
+// caracteres de control.
+fn write_json_string<W: Write>(out: &mut W, s: &str) -> io::Result<()> {
+    out.write_all(b"\"")?;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let escape: Cow<str> = match c {
+            '"' => Cow::Borrowed("\\\""),
+            '\\' => Cow::Borrowed("\\\\"),
+            '\n' => Cow::Borrowed("\\n"),
+            '\r' => Cow::Borrowed("\\r"),
+            '\t' => Cow::Borrowed("\\t"),
+            c if c < ' ' => Cow::Owned(format!("\\u{:04x}", c as u32)),
+            _ => continue,
+        };
+        out.write_all(&s.as_bytes()[start..i])?;
+        out.write_all(escape.as_bytes())?;
+        start = i + c.len_utf8();
+    }
+    out.write_all(&s.as_bytes()[start..])?;
+    out.write_all(b"\"")
+}
+
+// Un campo CSV (RFC 4180): si contiene comas, comillas o saltos de línea, va
+// entre comillas y las comillas internas se duplican.
+fn write_csv_field<W: Write>(out: &mut W, s: &str) -> io::Result<()> {
+    if s.contains([',', '"', '\n', '\r']) {
+        write!(out, "\"{}\"", s.replace('"', "\"\""))
+    } else {
+        out.write_all(s.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fallback, Rule};
+
+    fn formatted(rules: &FizzBuzzRules, n: u64, format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        rules.write_formatted_to(&mut out, n, format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    const NDJSON_15: &str = concat!(
+        "{\"n\":1,\"token\":\"1\",\"matched\":[]}\n",
+        "{\"n\":2,\"token\":\"2\",\"matched\":[]}\n",
+        "{\"n\":3,\"token\":\"fizz\",\"matched\":[3]}\n",
+        "{\"n\":4,\"token\":\"4\",\"matched\":[]}\n",
+        "{\"n\":5,\"token\":\"buzz\",\"matched\":[5]}\n",
+        "{\"n\":6,\"token\":\"fizz\",\"matched\":[3]}\n",
+        "{\"n\":7,\"token\":\"7\",\"matched\":[]}\n",
+        "{\"n\":8,\"token\":\"8\",\"matched\":[]}\n",
+        "{\"n\":9,\"token\":\"fizz\",\"matched\":[3]}\n",
+        "{\"n\":10,\"token\":\"buzz\",\"matched\":[5]}\n",
+        "{\"n\":11,\"token\":\"11\",\"matched\":[]}\n",
+        "{\"n\":12,\"token\":\"fizz\",\"matched\":[3]}\n",
+        "{\"n\":13,\"token\":\"13\",\"matched\":[]}\n",
+        "{\"n\":14,\"token\":\"14\",\"matched\":[]}\n",
+        "{\"n\":15,\"token\":\"fizzbuzz\",\"matched\":[3,5]}\n",
+    );
+
+    #[test]
+    fn golden_output_up_to_15() {
+        let rules = FizzBuzzRules::classic();
+        assert_eq!(formatted(&rules, 15, OutputFormat::Ndjson), NDJSON_15);
+
+        let json = format!("[\n{}\n]\n", NDJSON_15.trim_end().replace('\n', ",\n"));
+        assert_eq!(formatted(&rules, 15, OutputFormat::Json), json);
+        assert!(json.starts_with("[\n{\"n\":1,"));
+        assert!(json.ends_with("\"matched\":[3,5]}\n]\n"));
+
+        let csv = concat!(
+            "n,token,matched\n",
+            "1,1,\n2,2,\n3,fizz,3\n4,4,\n5,buzz,5\n6,fizz,3\n7,7,\n8,8,\n",
+            "9,fizz,3\n10,buzz,5\n11,11,\n12,fizz,3\n13,13,\n14,14,\n15,fizzbuzz,3;5\n",
+        );
+        assert_eq!(formatted(&rules, 15, OutputFormat::Csv), csv);
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let rules = FizzBuzzRules::classic();
+        assert_eq!(formatted(&rules, 0, OutputFormat::Json), "[]\n");
+        assert_eq!(formatted(&rules, 0, OutputFormat::Ndjson), "");
+        assert_eq!(formatted(&rules, 0, OutputFormat::Csv), "n,token,matched\n");
+        assert_eq!(formatted(&rules, 0, OutputFormat::Text), "");
+    }
+
+    #[test]
+    fn json_escapes() {
+        let mut out = Vec::new();
+        write_json_string(&mut out, "a\"b\\c\nd\re\tf\u{1}g\u{1f}ñ/").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#""a\"b\\c\nd\re\tf\u0001g\u001fñ/""#
+        );
+    }
+
+    #[test]
+    fn csv_quoting() {
+        let field = |s| {
+            let mut out = Vec::new();
+            write_csv_field(&mut out, s).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(field("fizz"), "fizz");
+        assert_eq!(field("a,b"), "\"a,b\"");
+        assert_eq!(field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(field("cr\r"), "\"cr\r\"");
+
+        let rules = FizzBuzzRules::new()
+            .rule(2, "a,\"b\"")
+            .with_fallback(Fallback::Text(String::new()));
+        assert_eq!(
+            formatted(&rules, 2, OutputFormat::Csv),
+            "n,token,matched\n1,,\n2,\"a,\"\"b\"\"\",2\n"
+        );
+    }
+
+    #[test]
+    fn matched_skips_overridden_rules() {
+        let rules = FizzBuzzRules::classic()
+            .with(
+                Rule::divisible_by(15, "bingo")
+                    .with_priority(1)
+                    .with_mode(RuleMode::Override),
+            )
+            .with(Rule::divisible_by(2, "two").with_priority(2));
+
+        let record = rules.record(30);
+        assert_eq!(
+            (record.token.as_ref(), record.matched),
+            ("bingotwo", vec![15, 2])
+        );
+        let record = rules.record(6);
+        assert_eq!(
+            (record.token.as_ref(), record.matched),
+            ("fizztwo", vec![3, 2])
+        );
+    }
+}
@@ -0,0 +1,506 @@
+// Juego de FizzBuzz por turnos.
+// =============================================================
+// Los jugadores cuentan por turnos a partir de 1 y cada uno debe decir el token
+// que corresponde a su número según las reglas (`FizzBuzzRules::token`). Quien se
+// equivoca queda eliminado y el siguiente jugador sigue con el mismo número; gana
+// el último que queda.
+//
+// • Una ronda termina cuando han jugado todos los jugadores que seguían en pie al
+//   empezarla. Con `Escalation`, cada `every_rounds` rondas se añade una regla
+//   nueva (7 = "bazz", 11 = "fuzz", ...), así que el juego se complica poco a poco.
+// • Los jugadores simulados (`Bot`) se equivocan con una probabilidad dada y tardan
+//   un tiempo aleatorio en contestar. Todo sale de un generador pseudoaleatorio con
+//   semilla (`Rng`), así que una partida con la misma semilla se repite igual.
+//
+// El motor no sabe nada de la terminal: recibe respuestas y devuelve `GameEvent`s.
+// El modo interactivo está en el binario (subcomando `game`).
+
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::{is_divisible_by, FizzBuzzRules};
+
+// SplitMix64: un generador pseudoaleatorio muy pequeño y de buena calidad, que nos
+// ahorra depender de `rand`. No sirve para criptografía.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Un número en `0.0..1.0`, con los 53 bits que caben en la mantisa de un `f64`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Un número en `range` (que no puede estar vacío).
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "el rango de `gen_range` está vacío");
+        // El sesgo del módulo es despreciable para rangos pequeños como los del juego.
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    // `true` con probabilidad `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bot {
+    // Probabilidad de equivocarse en cada turno (`0.0` = nunca).
+    pub error_rate: f64,
+    // Tiempo que tarda en contestar: un valor al azar en `min_delay..=max_delay`.
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Bot {
+    fn default() -> Bot {
+        Bot {
+            error_rate: 0.1,
+            min_delay: Duration::from_millis(300),
+            max_delay: Duration::from_millis(1200),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerKind {
+    Human,
+    Bot(Bot),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    pub name: String,
+    pub kind: PlayerKind,
+    pub alive: bool,
+    // Respuestas correctas.
+    pub score: u64,
+}
+
+impl Player {
+    pub fn human(name: impl Into<String>) -> Player {
+        Player::new(name, PlayerKind::Human)
+    }
+
+    pub fn bot(name: impl Into<String>, bot: Bot) -> Player {
+        Player::new(name, PlayerKind::Bot(bot))
+    }
+
+    fn new(name: impl Into<String>, kind: PlayerKind) -> Player {
+        Player {
+            name: name.into(),
+            kind,
+            alive: true,
+            score: 0,
+        }
+    }
+
+    pub fn is_bot(&self) -> bool {
+        matches!(self.kind, PlayerKind::Bot(_))
+    }
+}
+
+// Reglas que se van añadiendo durante la partida.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escalation {
+    // Cada cuántas rondas se añade una regla (`0` = nunca).
+    pub every_rounds: u64,
+    // Las reglas que se añaden, en orden: (divisor, palabra).
+    pub rules: Vec<(u64, String)>,
+}
+
+impl Default for Escalation {
+    fn default() -> Escalation {
+        Escalation {
+            every_rounds: 3,
+            rules: [(7, "bazz"), (11, "fuzz"), (13, "jazz"), (17, "whizz")]
+                .into_iter()
+                .map(|(divisor, word)| (divisor, word.to_owned()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    // `player` ha dicho bien el número `number`.
+    Correct {
+        player: usize,
+        number: u64,
+    },
+    // `player` se ha equivocado y queda eliminado.
+    Eliminated {
+        player: usize,
+        number: u64,
+        expected: String,
+        answer: String,
+    },
+    // Ha empezado la ronda `round` con una regla nueva.
+    RuleAdded {
+        round: u64,
+        divisor: u64,
+        word: String,
+    },
+    // Se acabó: gana `winner` (o nadie, si el último jugador se ha equivocado).
+    GameOver {
+        winner: Option<usize>,
+    },
+}
+
+// Lo que contesta un `Bot` y cuánto tarda en hacerlo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BotMove {
+    pub answer: String,
+    pub delay: Duration,
+}
+
+#[derive(Debug)]
+pub struct Game {
+    players: Vec<Player>,
+    rules: FizzBuzzRules,
+    escalation: Escalation,
+    // Reglas de `escalation` ya añadidas.
+    escalated: usize,
+    rng: Rng,
+    // Jugador al que le toca y número que tiene que decir.
+    current: usize,
+    number: u64,
+    // Ronda actual (desde 1) y turnos que quedan para terminarla.
+    round: u64,
+    turns_left: usize,
+    over: bool,
+}
+
+impl Game {
+    // Una partida con las reglas clásicas. Entra en pánico si no hay jugadores.
+    pub fn new(players: Vec<Player>, seed: u64) -> Game {
+        Game::with_rules(players, FizzBuzzRules::classic(), seed)
+    }
+
+    pub fn with_rules(players: Vec<Player>, rules: FizzBuzzRules, seed: u64) -> Game {
+        assert!(!players.is_empty(), "una partida necesita algún jugador");
+
+        let turns_left = players.len();
+        Game {
+            players,
+            rules,
+            escalation: Escalation {
+                every_rounds: 0,
+                rules: Vec::new(),
+            },
+            escalated: 0,
+            rng: Rng::new(seed),
+            current: 0,
+            number: 1,
+            round: 1,
+            turns_left,
+            over: false,
+        }
+    }
+
+    // Añade reglas cada cierto número de rondas (estilo `builder`).
+    pub fn with_escalation(mut self, escalation: Escalation) -> Game {
+        self.escalation = escalation;
+        self
+    }
+
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    pub fn rules(&self) -> &FizzBuzzRules {
+        &self.rules
+    }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    // El índice del jugador al que le toca (si la partida sigue).
+    pub fn current_player(&self) -> Option<usize> {
+        (!self.over).then_some(self.current)
+    }
+
+    // El número que hay que decir ahora.
+    pub fn number(&self) -> u64 {
+        self.number
+    }
+
+    // La respuesta correcta para el turno actual.
+    pub fn expected(&self) -> String {
+        self.rules.token(self.number).into_owned()
+    }
+
+    // ¿Es correcta `answer`? Se ignoran los espacios de alrededor y las mayúsculas.
+    pub fn is_correct(&self, answer: &str) -> bool {
+        answer.trim().eq_ignore_ascii_case(&self.expected())
+    }
+
+    // Lo que contestaría el jugador actual si es un `Bot` (no se aplica: hay que
+    // pasárselo a `submit`, quizá después de esperar `delay`).
+    pub fn bot_move(&mut self) -> Option<BotMove> {
+        let bot = match (&self.players.get(self.current)?.kind, self.over) {
+            (PlayerKind::Bot(bot), false) => bot.clone(),
+            _ => return None,
+        };
+
+        let answer = if self.rng.chance(bot.error_rate) {
+            self.wrong_answer()
+        } else {
+            self.expected()
+        };
+
+        let (min, max) = (
+            bot.min_delay.as_millis() as u64,
+            bot.max_delay.as_millis() as u64,
+        );
+        let delay = Duration::from_millis(self.rng.gen_range(min..max.max(min) + 1));
+        Some(BotMove { answer, delay })
+    }
+
+    // Un error creíble: el número en lugar de la palabra (o al revés), o una de
+    // las palabras de las reglas que no toca.
+    fn wrong_answer(&mut self) -> String {
+        let expected = self.expected();
+        let mut candidates = vec![self.number.to_string(), (self.number + 1).to_string()];
+        candidates.extend(
+            self.rules
+                .rules()
+                .iter()
+                .filter_map(|rule| rule.divisor_word())
+                .map(|(_, word)| word.to_owned()),
+        );
+        candidates.retain(|candidate| !candidate.eq_ignore_ascii_case(&expected));
+
+        let i = self.rng.gen_range(0..candidates.len() as u64) as usize;
+        candidates.swap_remove(i)
+    }
+
+    // Aplica la respuesta del jugador actual y devuelve lo que ha pasado.
+    pub fn submit(&mut self, answer: &str) -> Vec<GameEvent> {
+        if self.over {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        let player = self.current;
+        if self.is_correct(answer) {
+            self.players[player].score += 1;
+            events.push(GameEvent::Correct {
+                player,
+                number: self.number,
+            });
+            self.number += 1;
+        } else {
+            // El siguiente jugador sigue con el mismo número.
+            self.players[player].alive = false;
+            events.push(GameEvent::Eliminated {
+                player,
+                number: self.number,
+                expected: self.expected(),
+                answer: answer.trim().to_owned(),
+            });
+        }
+
+        let alive: Vec<usize> = (0..self.players.len())
+            .filter(|&i| self.players[i].alive)
+            .collect();
+        // Con varios jugadores se acaba al quedar uno; jugando solo, al fallar.
+        if alive.is_empty() || (alive.len() == 1 && self.players.len() > 1) {
+            self.over = true;
+            events.push(GameEvent::GameOver {
+                winner: alive.first().copied(),
+            });
+            return events;
+        }
+
+        self.turns_left -= 1;
+        if self.turns_left == 0 {
+            self.next_round(alive.len(), &mut events);
+        }
+        self.current = self.next_alive(player);
+        events
+    }
+
+    fn next_round(&mut self, alive: usize, events: &mut Vec<GameEvent>) {
+        self.round += 1;
+        self.turns_left = alive;
+
+        // Con `every_rounds == 0` nunca es divisible: no se añaden reglas.
+        if !is_divisible_by(self.round - 1, self.escalation.every_rounds) {
+            return;
+        }
+        if let Some((divisor, word)) = self.escalation.rules.get(self.escalated).cloned() {
+            self.escalated += 1;
+            self.rules.push(divisor, word.clone());
+            events.push(GameEvent::RuleAdded {
+                round: self.round,
+                divisor,
+                word,
+            });
+        }
+    }
+
+    // El siguiente jugador en pie después de `player`, dando la vuelta.
+    fn next_alive(&self, player: usize) -> usize {
+        let len = self.players.len();
+        (1..=len)
+            .map(|offset| (player + offset) % len)
+            .find(|&i| self.players[i].alive)
+            .unwrap_or(player)
+    }
+
+    // Juega sin esperas hasta que le toque a un humano o termine la partida
+    // (como mucho `max_turns` turnos) y devuelve lo que ha pasado.
+    pub fn play_bots(&mut self, max_turns: usize) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for _ in 0..max_turns {
+            match self.bot_move() {
+                Some(bot_move) => events.extend(self.submit(&bot_move.answer)),
+                None => break,
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn humans(n: usize) -> Vec<Player> {
+        (0..n).map(|i| Player::human(format!("p{i}"))).collect()
+    }
+
+    // Contesta bien `turns` veces y devuelve todos los eventos.
+    fn play_correctly(game: &mut Game, turns: usize) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for _ in 0..turns {
+            let answer = game.expected();
+            events.extend(game.submit(&answer));
+        }
+        events
+    }
+
+    #[test]
+    fn wrong_answer_eliminates_and_next_player_keeps_number() {
+        let mut game = Game::new(humans(3), 0);
+        assert_eq!(
+            game.submit("1"),
+            [GameEvent::Correct {
+                player: 0,
+                number: 1
+            }]
+        );
+        assert_eq!(
+            game.submit(" Fizz "),
+            [GameEvent::Eliminated {
+                player: 1,
+                number: 2,
+                expected: "2".to_owned(),
+                answer: "Fizz".to_owned(),
+            }]
+        );
+        assert!(!game.players()[1].alive);
+        assert_eq!((game.current_player(), game.number()), (Some(2), 2));
+
+        // La ronda 1 acaba tras los tres turnos; la 2 solo tiene a los dos que quedan.
+        assert_eq!(
+            game.submit("2"),
+            [GameEvent::Correct {
+                player: 2,
+                number: 2
+            }]
+        );
+        assert_eq!((game.round(), game.current_player()), (2, Some(0)));
+        play_correctly(&mut game, 2);
+        assert_eq!((game.round(), game.current_player()), (3, Some(0)));
+        assert_eq!(game.players()[0].score, 2);
+    }
+
+    #[test]
+    fn last_survivor_wins() {
+        let mut game = Game::new(humans(3), 0);
+        game.submit("2");
+        let events = game.submit("fizz");
+        assert_eq!(
+            events.last(),
+            Some(&GameEvent::GameOver { winner: Some(2) })
+        );
+        assert!(game.is_over());
+        assert_eq!(game.current_player(), None);
+        assert!(game.submit("1").is_empty());
+    }
+
+    #[test]
+    fn solo_game_ends_on_first_error() {
+        let mut game = Game::new(humans(1), 0);
+        assert_eq!(play_correctly(&mut game, 4).len(), 4);
+        assert!(!game.is_over());
+
+        let events = game.submit("5");
+        assert!(matches!(events[0], GameEvent::Eliminated { number: 5, .. }));
+        assert_eq!(events[1], GameEvent::GameOver { winner: None });
+    }
+
+    #[test]
+    fn bots_are_deterministic_with_a_seed() {
+        let play = |seed| {
+            let bot = Bot {
+                error_rate: 0.2,
+                ..Bot::default()
+            };
+            let players = (0..4).map(|i| Player::bot(format!("bot{i}"), bot.clone()));
+            let mut game =
+                Game::new(players.collect(), seed).with_escalation(Escalation::default());
+            game.play_bots(10_000)
+        };
+
+        let events = play(7);
+        assert_eq!(events, play(7));
+        assert!(matches!(
+            events.last(),
+            Some(GameEvent::GameOver { winner: Some(_) })
+        ));
+    }
+
+    #[test]
+    fn rules_are_added_every_few_rounds() {
+        let escalation = Escalation {
+            every_rounds: 2,
+            rules: vec![(7, "bazz".to_owned()), (11, "fuzz".to_owned())],
+        };
+        let mut game = Game::new(humans(2), 0).with_escalation(escalation);
+
+        // Siete rondas de dos turnos: se añade una regla al empezar las rondas 3 y 5.
+        let added: Vec<(u64, u64)> = play_correctly(&mut game, 14)
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::RuleAdded { round, divisor, .. } => Some((round, divisor)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(added, [(3, 7), (5, 11)]);
+        assert_eq!(game.round(), 8);
+        assert_eq!(game.rules().token(77), "bazzfuzz");
+    }
+}
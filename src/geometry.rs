@@ -0,0 +1,679 @@
+// Geometría en el plano.
+// =============================================================
+// `Point` (en `lib.rs`) es una posición y `Vector2` un desplazamiento. Los operadores
+// respetan esa diferencia, como en la geometría afín:
+// • punto - punto = vector (lo que hay que moverse para ir de uno a otro);
+// • punto ± vector = punto, y `+=` mueve el punto;
+// • vector ± vector = vector, y los vectores se escalan (`* k`) y se invierten (`-v`).
+// Multiplicar o negar un punto lo escala o lo refleja respecto al origen.
+//
+// Todo es genérico sobre el tipo de las coordenadas (`Scalar`): `f64` por defecto,
+// pero también `f32` o enteros para rejillas de píxeles. Lo que no se puede calcular
+// de forma exacta con esas operaciones (longitudes, ángulos) se devuelve en `f64`.
+//
+// VER: https://doc.rust-lang.org/stable/rust-by-example/trait/ops.html
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::{Point, Rectangle};
+
+// Un tipo numérico con el que se puede hacer geometría. Está implementado para los
+// enteros con signo y los `f32`/`f64`; otros tipos (p.ej. una coma fija para
+// importes) pueden implementarlo también.
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + fmt::Debug
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    // Como las de los enteros: `None` si el resultado no cabe en el tipo.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+
+    fn to_f64(self) -> f64;
+
+    // El valor más cercano a `value` (los enteros redondean y se saturan en sus límites).
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! impl_scalar_int {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: $t = 0;
+                const ONE: $t = 1;
+
+                fn checked_add(self, rhs: $t) -> Option<$t> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: $t) -> Option<$t> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: $t) -> Option<$t> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> $t {
+                    // `as` satura en los límites del tipo (y convierte `NaN` en `0`).
+                    value.round() as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_int!(i8, i16, i32, i64, i128, isize);
+
+// En coma flotante, "desbordarse" es llegar a infinito desde valores finitos.
+macro_rules! impl_scalar_float {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: $t = 0.0;
+                const ONE: $t = 1.0;
+
+                fn checked_add(self, rhs: $t) -> Option<$t> {
+                    finite(self, rhs, self + rhs)
+                }
+
+                fn checked_sub(self, rhs: $t) -> Option<$t> {
+                    finite(self, rhs, self - rhs)
+                }
+
+                fn checked_mul(self, rhs: $t) -> Option<$t> {
+                    finite(self, rhs, self * rhs)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(value: f64) -> $t {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_float!(f32, f64);
+
+fn finite<T: Scalar>(lhs: T, rhs: T, result: T) -> Option<T> {
+    let is_finite = |value: T| value.to_f64().is_finite();
+    (is_finite(result) || !is_finite(lhs) || !is_finite(rhs)).then_some(result)
+}
+
+// El valor absoluto de `a - b`, sin necesitar `abs` (que no tienen todos los tipos).
+pub(crate) fn abs_diff<T: Scalar>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+fn checked_abs_diff<T: Scalar>(a: T, b: T) -> Option<T> {
+    if a < b {
+        b.checked_sub(a)
+    } else {
+        a.checked_sub(b)
+    }
+}
+
+// `min` y `max` de `PartialOrd` (`Ord` no lo implementan los `f64`).
+fn min<T: Scalar>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: Scalar>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+// Error de las operaciones `checked_*`: el resultado no cabe en el tipo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.overflow"))
+    }
+}
+
+impl Error for Overflow {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vector2<T = f64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Scalar> Vector2<T> {
+    pub fn new(x: T, y: T) -> Vector2<T> {
+        Vector2 { x, y }
+    }
+
+    pub fn zero() -> Vector2<T> {
+        Vector2 {
+            x: T::ZERO,
+            y: T::ZERO,
+        }
+    }
+
+    // Producto escalar: `|a| |b| cos θ`.
+    pub fn dot(self, other: Vector2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    // Componente `z` del producto vectorial: `|a| |b| sen θ`. Es positiva si `other`
+    // está girado en sentido antihorario respecto a `self`.
+    pub fn cross(self, other: Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length_squared(self) -> T {
+        self.dot(self)
+    }
+
+    pub fn length(self) -> f64 {
+        self.x.to_f64().hypot(self.y.to_f64())
+    }
+
+    // El vector de longitud 1 con la misma dirección, o `None` para el vector nulo,
+    // que no tiene dirección.
+    pub fn normalize(self) -> Option<Vector2<f64>> {
+        let length = self.length();
+        let (x, y) = (self.x.to_f64(), self.y.to_f64());
+        (length > 0.0).then(|| Vector2::new(x / length, y / length))
+    }
+
+    // Interpolación lineal: `self` con `t = 0`, `other` con `t = 1` (con coordenadas
+    // enteras, redondeada).
+    pub fn lerp(self, other: Vector2<T>, t: f64) -> Vector2<T> {
+        let step = |from: T, to: T| T::from_f64((to - from).to_f64() * t);
+        self + Vector2::new(step(self.x, other.x), step(self.y, other.y))
+    }
+
+    // Ángulo con el eje `x`, en radianes, en `(-π, π]`.
+    pub fn angle(self) -> f64 {
+        self.y.to_f64().atan2(self.x.to_f64())
+    }
+
+    // Ángulo (con signo) que hay que girar `self` para llevarlo a la dirección de
+    // `other`, en radianes, en `(-π, π]`: positivo en sentido antihorario.
+    pub fn angle_to(self, other: Vector2<T>) -> f64 {
+        self.cross(other).to_f64().atan2(self.dot(other).to_f64())
+    }
+}
+
+impl<T: Scalar> Point<T> {
+    // El vector que va del origen al punto.
+    pub fn to_vector(self) -> Vector2<T> {
+        Vector2::new(self.x, self.y)
+    }
+
+    pub fn distance_squared(self, other: Point<T>) -> T {
+        (other - self).length_squared()
+    }
+
+    pub fn distance(self, other: Point<T>) -> f64 {
+        (other - self).length()
+    }
+
+    // El punto del segmento `self`–`other` en la proporción `t` (`0.5` es el punto medio).
+    pub fn lerp(self, other: Point<T>, t: f64) -> Point<T> {
+        self + Vector2::zero().lerp(other - self, t)
+    }
+
+    // Ángulo con el eje `x` de la dirección que va de `self` a `other`, en radianes,
+    // en `(-π, π]` (no es lo mismo que `Vector2::angle_to`, el ángulo entre dos vectores).
+    pub fn angle_to(self, other: Point<T>) -> f64 {
+        (other - self).angle()
+    }
+
+    // Lo mismo que `angle_to`, con un nombre que deja claro que es una dirección.
+    pub fn direction_to(self, other: Point<T>) -> f64 {
+        self.angle_to(other)
+    }
+
+    // `self + offset`, o `Overflow` si alguna coordenada no cabe.
+    pub fn checked_add(self, offset: Vector2<T>) -> Result<Point<T>, Overflow> {
+        match (self.x.checked_add(offset.x), self.y.checked_add(offset.y)) {
+            (Some(x), Some(y)) => Ok(Point::new(x, y)),
+            _ => Err(Overflow),
+        }
+    }
+}
+
+// Las esquinas de un `Rectangle` pueden venir en cualquier orden (`area` y
+// `perimeter` ya usan valores absolutos). Estos métodos trabajan con la forma
+// normalizada: `p1` es la esquina mínima y `p2` la máxima. El rectángulo es cerrado:
+// los bordes forman parte de él, así que dos rectángulos que solo se tocan se
+// cortan (en un segmento o un punto, con área `0`).
+impl<T: Scalar> Rectangle<T> {
+    pub fn new(p1: Point<T>, p2: Point<T>) -> Rectangle<T> {
+        Rectangle { p1, p2 }
+    }
+
+    // La esquina con las coordenadas más pequeñas.
+    pub fn min(&self) -> Point<T> {
+        Point::new(min(self.p1.x, self.p2.x), min(self.p1.y, self.p2.y))
+    }
+
+    // La esquina con las coordenadas más grandes.
+    pub fn max(&self) -> Point<T> {
+        Point::new(max(self.p1.x, self.p2.x), max(self.p1.y, self.p2.y))
+    }
+
+    // El mismo rectángulo con `p1 = min()` y `p2 = max()`: dos rectángulos iguales
+    // tienen la misma forma normalizada, aunque sus esquinas vengan en otro orden.
+    pub fn normalized(&self) -> Rectangle<T> {
+        Rectangle::new(self.min(), self.max())
+    }
+
+    pub fn is_normalized(&self) -> bool {
+        self.p1.x <= self.p2.x && self.p1.y <= self.p2.y
+    }
+
+    pub fn width(&self) -> T {
+        abs_diff(self.p1.x, self.p2.x)
+    }
+
+    pub fn height(&self) -> T {
+        abs_diff(self.p1.y, self.p2.y)
+    }
+
+    // Con coordenadas enteras, redondeado (desde `min()`, para que no dependa del
+    // orden de las esquinas).
+    pub fn center(&self) -> Point<T> {
+        self.min().lerp(self.max(), 0.5)
+    }
+
+    pub fn contains_point(&self, point: Point<T>) -> bool {
+        let (min, max) = (self.min(), self.max());
+        (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+    }
+
+    pub fn contains_rect(&self, other: &Rectangle<T>) -> bool {
+        self.contains_point(other.p1) && self.contains_point(other.p2)
+    }
+
+    pub fn intersects(&self, other: &Rectangle<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // La parte común (normalizada), o `None` si no se cortan.
+    pub fn intersection(&self, other: &Rectangle<T>) -> Option<Rectangle<T>> {
+        let (a, b) = (self.normalized(), other.normalized());
+        let low = Point::new(max(a.p1.x, b.p1.x), max(a.p1.y, b.p1.y));
+        let high = Point::new(min(a.p2.x, b.p2.x), min(a.p2.y, b.p2.y));
+        (low.x <= high.x && low.y <= high.y).then_some(Rectangle::new(low, high))
+    }
+
+    // El menor rectángulo (normalizado) que contiene a los dos.
+    pub fn union(&self, other: &Rectangle<T>) -> Rectangle<T> {
+        let (a, b) = (self.normalized(), other.normalized());
+        Rectangle::new(
+            Point::new(min(a.p1.x, b.p1.x), min(a.p1.y, b.p1.y)),
+            Point::new(max(a.p2.x, b.p2.x), max(a.p2.y, b.p2.y)),
+        )
+    }
+
+    // El rectángulo (normalizado) con cada lado desplazado `margin` hacia fuera.
+    // Con un margen negativo encoge; si encoge más que su tamaño, queda reducido a
+    // su centro en esa dimensión.
+    pub fn expand(&self, margin: T) -> Rectangle<T> {
+        let (low, high, center) = (self.min(), self.max(), self.center());
+        let side = |low: T, high: T, middle: T| {
+            if high - low + margin + margin < T::ZERO {
+                (middle, middle)
+            } else {
+                (low - margin, high + margin)
+            }
+        };
+        let (x1, x2) = side(low.x, high.x, center.x);
+        let (y1, y2) = side(low.y, high.y, center.y);
+        Rectangle::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    // Versiones de `area`, `perimeter` y `translate` que detectan el desbordamiento
+    // (útiles con coordenadas enteras, p.ej. `Rectangle<i32>`).
+
+    pub fn checked_area(&self) -> Result<T, Overflow> {
+        let width = checked_abs_diff(self.p1.x, self.p2.x).ok_or(Overflow)?;
+        let height = checked_abs_diff(self.p1.y, self.p2.y).ok_or(Overflow)?;
+        width.checked_mul(height).ok_or(Overflow)
+    }
+
+    pub fn checked_perimeter(&self) -> Result<T, Overflow> {
+        let width = checked_abs_diff(self.p1.x, self.p2.x).ok_or(Overflow)?;
+        let height = checked_abs_diff(self.p1.y, self.p2.y).ok_or(Overflow)?;
+        let half = width.checked_add(height).ok_or(Overflow)?;
+        half.checked_add(half).ok_or(Overflow)
+    }
+
+    // Si se desborda, el rectángulo no cambia.
+    pub fn checked_translate(&mut self, x: T, y: T) -> Result<(), Overflow> {
+        let offset = Vector2::new(x, y);
+        let (p1, p2) = (self.p1.checked_add(offset)?, self.p2.checked_add(offset)?);
+        *self = Rectangle::new(p1, p2);
+        Ok(())
+    }
+}
+
+// Vector ± vector.
+
+impl<T: Scalar> Add for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn add(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Scalar> Sub for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn sub(self, other: Vector2<T>) -> Vector2<T> {
+        Vector2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Scalar> AddAssign for Vector2<T> {
+    fn add_assign(&mut self, other: Vector2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Scalar> Mul<T> for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn mul(self, factor: T) -> Vector2<T> {
+        Vector2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Scalar> Neg for Vector2<T> {
+    type Output = Vector2<T>;
+
+    fn neg(self) -> Vector2<T> {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
+// Punto ± vector y punto - punto.
+
+impl<T: Scalar> Add<Vector2<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, offset: Vector2<T>) -> Point<T> {
+        Point::new(self.x + offset.x, self.y + offset.y)
+    }
+}
+
+impl<T: Scalar> Sub<Vector2<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, offset: Vector2<T>) -> Point<T> {
+        self + -offset
+    }
+}
+
+impl<T: Scalar> Sub for Point<T> {
+    type Output = Vector2<T>;
+
+    fn sub(self, other: Point<T>) -> Vector2<T> {
+        Vector2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Scalar> AddAssign<Vector2<T>> for Point<T> {
+    fn add_assign(&mut self, offset: Vector2<T>) {
+        *self = *self + offset;
+    }
+}
+
+// Escalado y reflexión respecto al origen.
+
+impl<T: Scalar> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Scalar> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn direction_to_and_angle_to() {
+        // Dirección de un punto a otro, respecto al eje `x`.
+        let p = Point::new(1.0, 1.0);
+        assert!(close(p.direction_to(Point::new(2.0, 2.0)), FRAC_PI_4));
+        assert!(close(p.direction_to(Point::new(0.0, 1.0)), PI));
+        assert!(close(p.angle_to(Point::new(1.0, 0.0)), -FRAC_PI_2));
+        assert_eq!(
+            p.angle_to(Point::new(3.0, 4.0)),
+            p.direction_to(Point::new(3.0, 4.0))
+        );
+
+        // Giro entre dos vectores, con signo.
+        let (x, y) = (Vector2::new(1.0, 0.0), Vector2::new(0.0, 3.0));
+        assert!(close(x.angle_to(y), FRAC_PI_2));
+        assert!(close(y.angle_to(x), -FRAC_PI_2));
+        assert!(close(
+            Vector2::new(2.0, 2.0).angle_to(Vector2::new(5.0, 5.0)),
+            0.0
+        ));
+    }
+
+    fn rect<T: Scalar>(x1: T, y1: T, x2: T, y2: T) -> Rectangle<T> {
+        Rectangle::new(Point::new(x1, y1), Point::new(x2, y2))
+    }
+
+    #[test]
+    fn swapped_corners() {
+        let swapped = rect(4.0, 2.0, 0.0, -1.0);
+        assert!(!swapped.is_normalized());
+        assert_eq!((swapped.area(), swapped.perimeter()), (12.0, 14.0));
+        assert_eq!((swapped.width(), swapped.height()), (4.0, 3.0));
+        assert_eq!(swapped.min(), Point::new(0.0, -1.0));
+        assert_eq!(swapped.max(), Point::new(4.0, 2.0));
+        assert_eq!(swapped.normalized(), rect(0.0, -1.0, 4.0, 2.0));
+        assert_eq!(swapped.center(), Point::new(2.0, 0.5));
+        assert!(swapped.contains_point(Point::new(0.0, 0.0)));
+        assert!(swapped.contains_rect(&rect(1.0, 1.0, 3.0, 0.0)));
+        assert!(!swapped.contains_rect(&rect(1.0, 1.0, 5.0, 0.0)));
+
+        // Con enteros, el centro se redondea igual sea cual sea el orden.
+        assert_eq!(rect(3, 3, 0, 0).center(), rect(0, 0, 3, 3).center());
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        assert_eq!(
+            a.intersection(&rect(3.0, 3.0, 1.0, 1.0)),
+            Some(rect(1.0, 1.0, 2.0, 2.0))
+        );
+
+        // Se tocan en un lado: se cortan, pero con área `0`.
+        let touching = a.intersection(&rect(2.0, 0.0, 4.0, 2.0)).unwrap();
+        assert_eq!(touching, rect(2.0, 0.0, 2.0, 2.0));
+        assert_eq!(touching.area(), 0.0);
+        assert!(a.intersects(&rect(2.0, 2.0, 3.0, 3.0)));
+
+        let disjoint = rect(2.5, 0.0, 4.0, 2.0);
+        assert_eq!(a.intersection(&disjoint), None);
+        assert!(!a.intersects(&disjoint));
+
+        assert_eq!(
+            a.union(&rect(4.0, -1.0, 3.0, 1.0)),
+            rect(0.0, -1.0, 4.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn expand_and_shrink() {
+        let r = rect(4.0, 2.0, 0.0, 0.0);
+        assert_eq!(r.expand(1.0), rect(-1.0, -1.0, 5.0, 3.0));
+        assert_eq!(r.expand(-0.5), rect(0.5, 0.5, 3.5, 1.5));
+        // La altura se queda en el centro; la anchura aún no.
+        assert_eq!(r.expand(-1.5), rect(1.5, 1.0, 2.5, 1.0));
+        assert_eq!(r.expand(-10.0), rect(2.0, 1.0, 2.0, 1.0));
+    }
+
+    #[test]
+    fn checked_operations_detect_overflow() {
+        let big = rect(0, 0, 70_000i32, 70_000);
+        assert_eq!(big.checked_area(), Err(Overflow));
+        assert_eq!(big.checked_perimeter(), Ok(280_000));
+        assert_eq!(
+            rect(0, 0, 40_000i32, 50_000).checked_area(),
+            Ok(2_000_000_000)
+        );
+        // El lado ya no cabe: `i32::MAX - i32::MIN`.
+        assert_eq!(rect(i32::MIN, 0, i32::MAX, 1).checked_area(), Err(Overflow));
+
+        let mut r = rect(0, 0, i32::MAX - 1, 10);
+        assert_eq!(r.checked_translate(1, 0), Ok(()));
+        assert_eq!(r, rect(1, 0, i32::MAX, 10));
+        // Si se desborda, no cambia.
+        assert_eq!(r.checked_translate(1, 0), Err(Overflow));
+        assert_eq!(r, rect(1, 0, i32::MAX, 10));
+
+        let p = Point::new(i32::MAX, 0);
+        assert_eq!(
+            p.checked_add(Vector2::new(0, 5)),
+            Ok(Point::new(i32::MAX, 5))
+        );
+        assert_eq!(p.checked_add(Vector2::new(1, 0)), Err(Overflow));
+    }
+
+    #[test]
+    fn f32_coordinates() {
+        let r: Rectangle<f32> = rect(0.0, 0.0, 3.0, -2.0);
+        assert_eq!((r.area(), r.perimeter()), (6.0, 10.0));
+        assert_eq!(r.checked_area(), Ok(6.0));
+        assert_eq!(r.center(), Point::new(1.5, -1.0));
+
+        // En coma flotante, desbordarse es llegar a infinito.
+        let huge = rect(0.0, 0.0, f32::MAX, f32::MAX);
+        assert_eq!(huge.checked_area(), Err(Overflow));
+        let mut moved = r;
+        assert_eq!(moved.checked_translate(1.5, 0.0), Ok(()));
+        assert_eq!(moved.p2, Point::new(4.5, -2.0));
+        assert_eq!(
+            Point::new(f32::MAX, 0.0).checked_add(Vector2::new(f32::MAX, 0.0)),
+            Err(Overflow)
+        );
+    }
+
+    #[test]
+    fn vector_operators() {
+        let (a, b) = (Vector2::new(1, 2), Vector2::new(3, -4));
+        assert_eq!(a + b, Vector2::new(4, -2));
+        assert_eq!(a - b, Vector2::new(-2, 6));
+        assert_eq!(a * 3, Vector2::new(3, 6));
+        assert_eq!(-a, Vector2::new(-1, -2));
+        let mut c = a;
+        c += b;
+        assert_eq!(c, Vector2::new(4, -2));
+        assert_eq!(Vector2::<i32>::zero(), Vector2::new(0, 0));
+
+        let p = Point::new(1, 1);
+        assert_eq!(p + a, Point::new(2, 3));
+        assert_eq!(p - a, Point::new(0, -1));
+        assert_eq!(Point::new(4, 5) - p, Vector2::new(3, 4));
+        assert_eq!(p * -2, Point::new(-2, -2));
+        assert_eq!(-p, Point::new(-1, -1));
+        let mut q = p;
+        q += b;
+        assert_eq!(q, Point::new(4, -3));
+        assert_eq!(q.to_vector(), Vector2::new(4, -3));
+    }
+
+    #[test]
+    fn vector_methods() {
+        let (a, b) = (Vector2::new(3.0, 4.0), Vector2::new(-4.0, 3.0));
+        assert_eq!(a.dot(b), 0.0);
+        assert_eq!(a.dot(a), 25.0);
+        assert_eq!(a.cross(b), 25.0);
+        assert_eq!(b.cross(a), -25.0);
+        assert_eq!((a.length(), a.length_squared()), (5.0, 25.0));
+        assert!(close(Vector2::new(0.0, 2.0).angle(), FRAC_PI_2));
+
+        assert_eq!(a.normalize(), Some(Vector2::new(0.6, 0.8)));
+        assert_eq!(
+            Vector2::new(0, -7).normalize(),
+            Some(Vector2::new(0.0, -1.0))
+        );
+        assert_eq!(Vector2::<f64>::zero().normalize(), None);
+        assert_eq!(Vector2::new(0, 0).normalize(), None);
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vector2::new(-0.5, 3.5));
+        // Con enteros, redondeado.
+        assert_eq!(
+            Vector2::new(0, 0).lerp(Vector2::new(3, 1), 0.5),
+            Vector2::new(2, 1)
+        );
+    }
+
+    #[test]
+    fn point_distance_and_lerp() {
+        let (p, q) = (Point::new(1.0, 1.0), Point::new(4.0, 5.0));
+        assert_eq!(p.distance(q), 5.0);
+        assert_eq!(p.distance_squared(q), 25.0);
+        assert_eq!(Point::new(0, 0).distance_squared(Point::new(-3, 4)), 25);
+        assert_eq!(p.lerp(q, 0.5), Point::new(2.5, 3.0));
+        assert_eq!(p.lerp(q, 2.0), Point::new(7.0, 9.0));
+
+        // `translate` se expresa con vectores.
+        let mut r = Rectangle::new(p, q);
+        r.translate(1.0, -1.0);
+        assert_eq!(
+            r,
+            Rectangle::new(Point::new(2.0, 0.0), Point::new(5.0, 4.0))
+        );
+    }
+}
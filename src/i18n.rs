@@ -0,0 +1,565 @@
+// Mensajes traducidos.
+// =============================================================
+// Un catálogo pequeño de mensajes: cada clave (`"pair.destroy"`) tiene un texto por
+// idioma, con huecos numerados (`{0}`, `{1}`, ...) que se rellenan al formatearlo.
+// Si a un idioma le falta una clave, se usa el texto en español; si tampoco existe,
+// se muestra la propia clave, para que el hueco se note sin romper nada.
+//
+// El idioma es global (`set_locale`), porque lo necesitan funciones como
+// `Pair::destroy` o los cierres de `create_fn`, que no reciben parámetros.
+//
+// Las palabras de FizzBuzz ("fizz", "buzz") no se traducen: forman parte del formato
+// de salida que comprueban `verify` y los generadores rápidos.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    Es,
+    En,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownLocale(pub String);
+
+impl fmt::Display for UnknownLocale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.unknown_locale", self.0))
+    }
+}
+
+impl Error for UnknownLocale {}
+
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(s: &str) -> Result<Locale, UnknownLocale> {
+        match s {
+            "es" => Ok(Locale::Es),
+            "en" => Ok(Locale::En),
+            other => Err(UnknownLocale(other.to_owned())),
+        }
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::Es as u8);
+
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match LOCALE.load(Ordering::Relaxed) {
+        l if l == Locale::En as u8 => Locale::En,
+        _ => Locale::Es,
+    }
+}
+
+// El catálogo en español está completo: es el de referencia.
+const ES: &[(&str, &str)] = &[
+    ("error", "Error: {0}"),
+    // Errores de la línea de órdenes.
+    ("error.unknown_command", "subcomando desconocido: `{0}`"),
+    ("error.invalid_number", "`{0}` no es un número válido: {1}"),
+    ("error.missing_value", "falta el valor de `{0}`"),
+    ("error.missing_n", "falta el argumento `n`"),
+    ("error.missing_start", "falta el argumento `inicio`"),
+    ("error.read_file", "no se pudo leer `{0}`: {1}"),
+    ("error.open_file", "no se pudo abrir `{0}`: {1}"),
+    // El fichero y el `ParseError` (ya traducido); igual en inglés.
+    ("error.rules_file", "{0}: {1}"),
+    (
+        "error.output_format",
+        "`--output` solo admite el formato `text`",
+    ),
+    (
+        "error.resume_needs_output",
+        "`--resume` necesita `--output <fichero>`",
+    ),
+    ("error.bench_mismatch", "resultados distintos: {0} y {1}"),
+    ("error.error_rate", "`--error-rate` debe estar entre 0 y 1"),
+    ("error.no_players", "hace falta algún jugador"),
+    // Errores de la biblioteca.
+    ("error.division_by_zero", "división por cero"),
+    ("error.overflow", "desbordamiento aritmético"),
+    (
+        "error.unknown_locale",
+        "idioma desconocido `{0}` (se esperaba `es` o `en`)",
+    ),
+    (
+        "error.unknown_format",
+        "formato desconocido `{0}` (se esperaba `text`, `json`, `ndjson` o `csv`)",
+    ),
+    (
+        "error.unknown_number_format",
+        "formato de número desconocido `{0}` (se esperaba `decimal`, `binary`, `octal`, \
+         `hex`, `roman`, `spanish` o `english`)",
+    ),
+    (
+        "error.unknown_strictness",
+        "nivel de exigencia desconocido `{0}` (se esperaba `strict`, `normal` o `lenient`)",
+    ),
+    (
+        "error.parse_biguint",
+        "`{0}` no es un número natural en decimal",
+    ),
+    ("dsl.error", "línea {0}, columna {1}: {2}"),
+    ("dsl.number_too_big", "número demasiado grande"),
+    ("dsl.unexpected_char", "carácter inesperado `{0}`"),
+    ("dsl.unterminated_string", "cadena sin cerrar"),
+    (
+        "dsl.unknown_escape",
+        "secuencia de escape desconocida `\\{0}`",
+    ),
+    ("dsl.expected", "se esperaba {0}, pero se encontró {1}"),
+    ("dsl.zero_divisor", "el divisor no puede ser 0"),
+    ("dsl.unknown_key", "clave desconocida `{0}`"),
+    ("dsl.expected.separator", "`,`, `;` o un salto de línea"),
+    (
+        "dsl.expected.fallback",
+        "`number`, `empty` o un texto entre comillas",
+    ),
+    ("dsl.expected.key", "un divisor o una clave"),
+    ("dsl.expected.word", "una palabra"),
+    ("dsl.token.number", "el número `{0}`"),
+    ("dsl.token.text", "el texto {0}"),
+    ("dsl.token.newline", "un salto de línea"),
+    ("dsl.token.end", "el final del texto"),
+    (
+        "resume.error.not_resumable",
+        "las reglas con cierres no se pueden reanudar",
+    ),
+    (
+        "resume.error.no_checkpoint",
+        "`{0}` ya existe, pero no tiene punto de control",
+    ),
+    (
+        "resume.error.rules_changed",
+        "las reglas ({0}) no son las del punto de control ({1})",
+    ),
+    ("resume.error.corrupt", "punto de control no válido: {0}"),
+    (
+        "resume.error.tail_mismatch",
+        "el final del fichero no coincide con FizzBuzz (línea {0})",
+    ),
+    (
+        "resume.error.beyond_end",
+        "el fichero ya llega hasta la línea {0}, más allá de {1}",
+    ),
+    ("resume.corrupt.header", "falta la cabecera"),
+    ("resume.corrupt.field", "falta `{0}` o no es válido"),
+    ("resume.corrupt.offset", "el byte no corresponde a la línea"),
+    (
+        "resume.corrupt.short",
+        "el fichero es más corto de lo que indica",
+    ),
+    (
+        "verify.wrong_token",
+        "línea {0}: se esperaba {1}, pero se encontró {2}",
+    ),
+    (
+        "verify.missing_line",
+        "línea {0}: se esperaba {1}, pero la entrada termina antes",
+    ),
+    ("verify.extra_line", "línea {0}: sobra la línea {1}"),
+    (
+        "verify.carriage_return",
+        "línea {0}: final de línea `\\r\\n` no admitido",
+    ),
+    (
+        "verify.missing_final_newline",
+        "línea {0}: falta el salto de línea final",
+    ),
+    (
+        "verify.invalid_utf8",
+        "línea {0}: la línea no es UTF-8 válido",
+    ),
+    ("pair.destroy", "Destruyendo `Pair`: Pair({0}, {1})"),
+    ("closure.this_is", "Esto es un: {0}"),
+    ("verify.ok", "Correcto: {0} líneas comprobadas."),
+    ("bench.header", "FizzBuzz hasta {0} ({1} bytes):"),
+    ("bench.fast", "generador rápido:"),
+    ("bench.parallel", "en paralelo ({0} hilos):"),
+    (
+        "bench.divisors.header",
+        "Divisibilidad por {0} de 1 a {1} (múltiplos: {2}):",
+    ),
+    ("bench.divisors.remainder", "`is_divisible_by` (`%`):"),
+    ("bench.divisors.inverse", "`Divisor::divides` (inverso):"),
+    ("bench.numbers_per_second", "M números/s"),
+    ("resume.resumed", "Reanudando tras la línea {0} (byte {1})."),
+    ("resume.done", "`{0}`: {1} líneas, {2} bytes."),
+    ("game.default_player", "jugador"),
+    ("game.bot_name", "bot {0}"),
+    ("game.start", "Partida con {0} (semilla {1})."),
+    ("game.prompt", "{0}, te toca (número {1}): "),
+    ("game.says", "{0}: {1}"),
+    ("game.eliminated", "¡Fuera {0}! Tocaba `{1}` y dijo `{2}`."),
+    (
+        "game.rule_added",
+        "Ronda {0}: nueva regla, los múltiplos de {1} son `{2}`.",
+    ),
+    ("game.winner", "¡Gana {0} con {1} aciertos!"),
+    (
+        "game.no_winner",
+        "Nadie gana: la partida terminó en el número {0}.",
+    ),
+    ("game.abandoned", "Partida abandonada."),
+    ("demo.rectangle.perimeter", "Perímetro del rectángulo: {0}"),
+    ("demo.rectangle.area", "Área del rectángulo: {0}"),
+    ("demo.rectangle.diagonal", "Diagonal del rectángulo: {0}"),
+    (
+        "demo.rectangle.checked_area",
+        "Área de un rectángulo de 70000 × 70000 píxeles (`i32`): {0}",
+    ),
+    (
+        "demo.scene.total",
+        "Escena con {0} figuras: área total {1}, área cubierta {2}.",
+    ),
+    (
+        "demo.scene.overlap",
+        "Las figuras {0} y {1} se solapan en {2}.",
+    ),
+    (
+        "demo.transform.rotated",
+        "Rectángulo de 4 × 3 girado 45°: área {0}, caja envolvente de {1} × {2}.",
+    ),
+    ("demo.function", "`function`: {0}"),
+    ("demo.closure_annotated", "`closure_annotated`: {0}"),
+    ("demo.closure_inferred", "`closure_inferred`: {0}"),
+    ("demo.closure_one", "`Closure` que devuelve `1`: {0}"),
+    ("demo.color", "`color` es: {0}"),
+    ("demo.count", "`count`: {0}"),
+    ("demo.movable", "`movable`: {0}"),
+    ("demo.said", "Dije {0}."),
+    ("demo.screamed", "Luego grité {0}."),
+    ("demo.sleep", "Ahora puedo dormir. zzzzz"),
+    ("demo.double", "El doble de 3 es: {0}"),
+    ("demo.i_am_function", "¡Soy una función!"),
+    ("demo.i_am_closure", "¡Soy un cierre!"),
+    ("demo.closure_rules", "FizzBuzz con cierres (21 a 40): {0}"),
+    ("demo.any", "¿Está `2` en `{0}`: {1}"),
+    ("demo.find", "Encontrado `2` en `{0}`: {1}"),
+    ("demo.first_even", "Índice del primer número par: {0}"),
+    (
+        "demo.first_negative",
+        "Índice de primer número negativo: {0}",
+    ),
+    (
+        "demo.squared_odd",
+        "Encuentra la suma de todos los números impares al cuadrado menores de 1000.",
+    ),
+    (
+        "demo.imperative",
+        "Resultado obtenido en estilo imperativo: {0}",
+    ),
+    (
+        "demo.functional",
+        "Resultado obtenido en estilo funcional: {0}",
+    ),
+    (
+        "demo.plain_sum",
+        "Suma de los números de FizzBuzz hasta 100: {0}",
+    ),
+    ("demo.countdown", "FizzBuzz de 7 en 7, al revés: {0}"),
+    (
+        "demo.const_table",
+        "Calculado al compilar: el 15 es `{0}` y la salida tiene {1} líneas.",
+    ),
+    (
+        "demo.odd_sum",
+        "La suma de los impares hasta 9, excluido: {0}",
+    ),
+];
+
+// Los mensajes que son iguales en los dos idiomas (p.ej. "`count`: {0}") no hace
+// falta repetirlos: se toman del español.
+const EN: &[(&str, &str)] = &[
+    ("error.unknown_command", "unknown subcommand: `{0}`"),
+    ("error.invalid_number", "`{0}` is not a valid number: {1}"),
+    ("error.missing_value", "missing value for `{0}`"),
+    ("error.missing_n", "missing argument `n`"),
+    ("error.missing_start", "missing argument `start`"),
+    ("error.read_file", "could not read `{0}`: {1}"),
+    ("error.open_file", "could not open `{0}`: {1}"),
+    (
+        "error.output_format",
+        "`--output` only supports the `text` format",
+    ),
+    (
+        "error.resume_needs_output",
+        "`--resume` needs `--output <file>`",
+    ),
+    ("error.bench_mismatch", "results differ: {0} and {1}"),
+    ("error.error_rate", "`--error-rate` must be between 0 and 1"),
+    ("error.no_players", "at least one player is needed"),
+    ("error.division_by_zero", "division by zero"),
+    ("error.overflow", "arithmetic overflow"),
+    (
+        "error.unknown_locale",
+        "unknown language `{0}` (expected `es` or `en`)",
+    ),
+    (
+        "error.unknown_format",
+        "unknown format `{0}` (expected `text`, `json`, `ndjson` or `csv`)",
+    ),
+    (
+        "error.unknown_number_format",
+        "unknown number format `{0}` (expected `decimal`, `binary`, `octal`, `hex`, \
+         `roman`, `spanish` or `english`)",
+    ),
+    (
+        "error.unknown_strictness",
+        "unknown strictness `{0}` (expected `strict`, `normal` or `lenient`)",
+    ),
+    (
+        "error.parse_biguint",
+        "`{0}` is not a decimal natural number",
+    ),
+    ("dsl.error", "line {0}, column {1}: {2}"),
+    ("dsl.number_too_big", "number too large"),
+    ("dsl.unexpected_char", "unexpected character `{0}`"),
+    ("dsl.unterminated_string", "unterminated string"),
+    ("dsl.unknown_escape", "unknown escape sequence `\\{0}`"),
+    ("dsl.expected", "expected {0}, found {1}"),
+    ("dsl.zero_divisor", "the divisor cannot be 0"),
+    ("dsl.unknown_key", "unknown key `{0}`"),
+    ("dsl.expected.separator", "`,`, `;` or a line break"),
+    (
+        "dsl.expected.fallback",
+        "`number`, `empty` or a quoted text",
+    ),
+    ("dsl.expected.key", "a divisor or a key"),
+    ("dsl.expected.word", "a word"),
+    ("dsl.token.number", "the number `{0}`"),
+    ("dsl.token.text", "the text {0}"),
+    ("dsl.token.newline", "a line break"),
+    ("dsl.token.end", "the end of the text"),
+    (
+        "resume.error.not_resumable",
+        "rules with closures cannot be resumed",
+    ),
+    (
+        "resume.error.no_checkpoint",
+        "`{0}` already exists but has no checkpoint",
+    ),
+    (
+        "resume.error.rules_changed",
+        "the rules ({0}) are not those of the checkpoint ({1})",
+    ),
+    ("resume.error.corrupt", "invalid checkpoint: {0}"),
+    (
+        "resume.error.tail_mismatch",
+        "the end of the file does not match FizzBuzz (line {0})",
+    ),
+    (
+        "resume.error.beyond_end",
+        "the file already reaches line {0}, beyond {1}",
+    ),
+    ("resume.corrupt.header", "missing header"),
+    ("resume.corrupt.field", "`{0}` is missing or invalid"),
+    (
+        "resume.corrupt.offset",
+        "the byte offset does not match the line",
+    ),
+    ("resume.corrupt.short", "the file is shorter than recorded"),
+    ("verify.wrong_token", "line {0}: expected {1}, found {2}"),
+    (
+        "verify.missing_line",
+        "line {0}: expected {1}, but the input ends earlier",
+    ),
+    ("verify.extra_line", "line {0}: unexpected extra line {1}"),
+    (
+        "verify.carriage_return",
+        "line {0}: `\\r\\n` line ending not allowed",
+    ),
+    (
+        "verify.missing_final_newline",
+        "line {0}: missing final line break",
+    ),
+    (
+        "verify.invalid_utf8",
+        "line {0}: the line is not valid UTF-8",
+    ),
+    ("pair.destroy", "Destroying `Pair`: Pair({0}, {1})"),
+    ("closure.this_is", "This is a: {0}"),
+    ("verify.ok", "OK: {0} lines checked."),
+    ("bench.header", "FizzBuzz up to {0} ({1} bytes):"),
+    ("bench.fast", "fast generator:"),
+    ("bench.parallel", "parallel ({0} threads):"),
+    (
+        "bench.divisors.header",
+        "Divisibility by {0} from 1 to {1} (multiples: {2}):",
+    ),
+    ("bench.divisors.inverse", "`Divisor::divides` (inverse):"),
+    ("bench.numbers_per_second", "M numbers/s"),
+    ("resume.resumed", "Resuming after line {0} (byte {1})."),
+    ("resume.done", "`{0}`: {1} lines, {2} bytes."),
+    ("game.default_player", "player"),
+    ("game.start", "Game with {0} (seed {1})."),
+    ("game.prompt", "{0}, your turn (number {1}): "),
+    (
+        "game.eliminated",
+        "{0} is out! It was `{1}` and they said `{2}`.",
+    ),
+    (
+        "game.rule_added",
+        "Round {0}: new rule, multiples of {1} are `{2}`.",
+    ),
+    ("game.winner", "{0} wins with {1} correct answers!"),
+    (
+        "game.no_winner",
+        "Nobody wins: the game ended at number {0}.",
+    ),
+    ("game.abandoned", "Game abandoned."),
+    ("demo.rectangle.perimeter", "Rectangle perimeter: {0}"),
+    ("demo.rectangle.area", "Rectangle area: {0}"),
+    ("demo.rectangle.diagonal", "Rectangle diagonal: {0}"),
+    (
+        "demo.scene.total",
+        "Scene with {0} shapes: total area {1}, covered area {2}.",
+    ),
+    ("demo.scene.overlap", "Shapes {0} and {1} overlap by {2}."),
+    (
+        "demo.transform.rotated",
+        "4 × 3 rectangle rotated 45°: area {0}, bounding box {1} × {2}.",
+    ),
+    (
+        "demo.rectangle.checked_area",
+        "Area of a 70000 × 70000 pixel rectangle (`i32`): {0}",
+    ),
+    ("demo.closure_one", "`Closure` returning `1`: {0}"),
+    ("demo.color", "`color` is: {0}"),
+    ("demo.said", "I said {0}."),
+    ("demo.screamed", "Then I screamed {0}."),
+    ("demo.sleep", "Now I can sleep. zzzzz"),
+    ("demo.double", "3 doubled is: {0}"),
+    ("demo.i_am_function", "I'm a function!"),
+    ("demo.i_am_closure", "I'm a closure!"),
+    (
+        "demo.closure_rules",
+        "FizzBuzz with closures (21 to 40): {0}",
+    ),
+    ("demo.any", "Is `2` in `{0}`: {1}"),
+    ("demo.find", "Found `2` in `{0}`: {1}"),
+    ("demo.first_even", "Index of the first even number: {0}"),
+    (
+        "demo.first_negative",
+        "Index of the first negative number: {0}",
+    ),
+    (
+        "demo.squared_odd",
+        "Find the sum of all the squared odd numbers under 1000.",
+    ),
+    ("demo.imperative", "Result in imperative style: {0}"),
+    ("demo.functional", "Result in functional style: {0}"),
+    (
+        "demo.plain_sum",
+        "Sum of the FizzBuzz numbers up to 100: {0}",
+    ),
+    ("demo.countdown", "FizzBuzz in steps of 7, reversed: {0}"),
+    (
+        "demo.const_table",
+        "Computed at compile time: 15 is `{0}` and the output has {1} lines.",
+    ),
+    (
+        "demo.odd_sum",
+        "Sum of the odd numbers up to 9, excluded: {0}",
+    ),
+];
+
+fn catalog(locale: Locale) -> &'static [(&'static str, &'static str)] {
+    match locale {
+        Locale::Es => ES,
+        Locale::En => EN,
+    }
+}
+
+// El texto de `key` en el idioma actual, con los huecos sin rellenar.
+pub fn message_template(key: &str) -> &str {
+    let find = |catalog: &'static [(&str, &'static str)]| {
+        catalog
+            .iter()
+            .find(|&&(other, _)| other == key)
+            .map(|&(_, text)| text)
+    };
+
+    find(catalog(locale())).or_else(|| find(ES)).unwrap_or(key)
+}
+
+// El texto de `key` en el idioma actual, con `{i}` sustituido por `args[i]`.
+// Un hueco sin argumento se deja tal cual.
+pub fn format_message(key: &str, args: &[&dyn Display]) -> String {
+    let template = message_template(key);
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        message.push_str(&rest[..open]);
+        let hole = rest[open + 1..].find('}').and_then(|close| {
+            let index = rest[open + 1..open + 1 + close].parse::<usize>().ok()?;
+            Some((index, open + 1 + close))
+        });
+        match hole.and_then(|(index, close)| Some((args.get(index)?, close))) {
+            Some((arg, close)) => {
+                message.push_str(&arg.to_string());
+                rest = &rest[close + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+// `message!("pair.destroy", first, second)`: atajo de `format_message`.
+#[macro_export]
+macro_rules! message {
+    ($key:expr $(, $arg:expr)* $(,)?) => {
+        $crate::format_message($key, &[$(&$arg as &dyn ::std::fmt::Display),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogs_are_consistent() {
+        // Sin claves repetidas, y el inglés no tiene claves que falten en español.
+        for catalog in [ES, EN] {
+            for (i, (key, _)) in catalog.iter().enumerate() {
+                assert!(
+                    catalog[..i].iter().all(|(other, _)| other != key),
+                    "clave repetida `{key}`"
+                );
+            }
+        }
+        for (key, _) in EN {
+            assert!(
+                ES.iter().any(|(other, _)| other == key),
+                "`{key}` no está en ES"
+            );
+        }
+    }
+
+    #[test]
+    fn error_messages_follow_the_locale() {
+        let text = || crate::DivisionByZero.to_string();
+        set_locale(Locale::En);
+        let en = text();
+        set_locale(Locale::Es);
+        let es = text();
+        assert_eq!(
+            (es.as_str(), en.as_str()),
+            ("división por cero", "division by zero")
+        );
+    }
+}
@@ -2,38 +2,411 @@
 // =============================================================
 // VER: https://doc.rust-lang.org/stable/rust-by-example/fn.html
 
-// Una función que devuelve un valor booleano:
-pub fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
-    // Caso extremo: división por `0`:
-    if rhs == 0 {
-        return false;
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+use std::iter::FusedIterator;
+use std::ops::{Bound, RangeBounds};
+use std::sync::OnceLock;
+
+mod bignum;
+mod divisor;
+mod dsl;
+mod format;
+mod game;
+mod geometry;
+mod i18n;
+mod number_theory;
+mod numeral;
+mod parallel;
+mod resume;
+mod rules;
+mod shapes;
+mod stats;
+mod stream;
+mod table;
+mod transform;
+mod verify;
+
+pub use bignum::{BigFizzBuzz, BigToken, BigUint, ParseBigUintError};
+pub use divisor::Divisor;
+pub use dsl::{parse_rules, ParseError};
+pub use format::{OutputFormat, Record, RecordWriter, UnknownFormat};
+pub use game::{Bot, BotMove, Escalation, Game, GameEvent, Player, PlayerKind, Rng};
+use geometry::abs_diff;
+pub use geometry::{Overflow, Scalar, Vector2};
+pub use i18n::{format_message, locale, message_template, set_locale, Locale, UnknownLocale};
+pub use number_theory::{
+    checked_lcm, divisors, extended_gcd, factorize, gcd, is_prime, lcm, mod_inverse, primes,
+    totient, Divisors, ExtendedGcd, Integer, Primes,
+};
+pub use numeral::{FormattedNumber, NumberFormat, UnknownNumberFormat};
+pub use parallel::{write_fizzbuzz_parallel, write_fizzbuzz_range_parallel, ParallelConfig};
+pub use resume::{write_fizzbuzz_resumable, Checkpoint, ResumeError, ResumeOptions, ResumeReport};
+pub use rules::{
+    contains_digit, is_palindrome, is_perfect_square, Fallback, FizzBuzzRules, Rule, RuleFn,
+    RuleMode,
+};
+pub use shapes::{Circle, Ellipse, Overlap, Polygon, Scene, SceneReport, Shape, Triangle};
+pub use stats::{fizzbuzz_stats, RuleStats, Stats, MAX_STATS_RULES};
+pub use stream::{write_fizzbuzz_fast, write_fizzbuzz_range_fast};
+pub use table::{fizzbuzz_bytes, fizzbuzz_table};
+pub use transform::Transform2D;
+pub use verify::{
+    verify, ContextLine, Mismatch, MismatchKind, Strictness, UnknownStrictness, VerifyOptions,
+    VerifyReport,
+};
+
+// Error de dividir por `0`: permite distinguir "no es divisible" de "entrada no válida".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DivisionByZero;
+
+impl fmt::Display for DivisionByZero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.division_by_zero"))
     }
+}
+
+impl std::error::Error for DivisionByZero {}
+
+// Divisibilidad para cualquier tipo entero primitivo, con o sin signo.
+pub trait Divisible: Copy {
+    // `Err(DivisionByZero)` si `rhs` es `0`.
+    fn checked_is_divisible_by(self, rhs: Self) -> Result<bool, DivisionByZero>;
+
+    // Versión "silenciosa": dividir por `0` se considera "no divisible".
+    fn is_divisible_by(self, rhs: Self) -> bool {
+        self.checked_is_divisible_by(rhs).unwrap_or(false)
+    }
+}
+
+// Implementamos el rasgo para todos los enteros con una macro, ya que el cuerpo es idéntico.
+macro_rules! impl_divisible {
+    ($($t:ty),*) => {
+        $(
+            impl Divisible for $t {
+                fn checked_is_divisible_by(self, rhs: $t) -> Result<bool, DivisionByZero> {
+                    if rhs == 0 {
+                        return Err(DivisionByZero);
+                    }
+
+                    // `wrapping_rem` evita el desbordamiento de `MIN % -1` en los tipos con signo.
+                    Ok(self.wrapping_rem(rhs) == 0)
+                }
+            }
+        )*
+    };
+}
+
+impl_divisible!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// Una función que devuelve un valor booleano. Es `const fn` para poder usarla al
+// compilar (ver `fizzbuzz_table`); como los métodos de un rasgo todavía no pueden
+// ser `const`, para otros tipos enteros está `Divisible::is_divisible_by`.
+pub const fn is_divisible_by(lhs: u64, rhs: u64) -> bool {
+    // Caso extremo: la división por `0` devuelve `false`
+    // (usa `checked_is_divisible_by` para detectarla).
+    rhs != 0 && lhs.is_multiple_of(rhs)
+}
+
+pub fn checked_is_divisible_by<T: Divisible>(lhs: T, rhs: T) -> Result<bool, DivisionByZero> {
+    lhs.checked_is_divisible_by(rhs)
+}
+
+// Las reglas clásicas se construyen una sola vez y se comparten, de modo que
+// `fizzbuzz_str` puede devolver prestadas (`'static`) las palabras "fizz" y "buzz".
+fn classic_rules() -> &'static FizzBuzzRules {
+    static CLASSIC: OnceLock<FizzBuzzRules> = OnceLock::new();
+    CLASSIC.get_or_init(FizzBuzzRules::classic)
+}
+
+// Devuelve el token en lugar de imprimirlo.
+pub fn fizzbuzz_str(n: u32) -> Cow<'static, str> {
+    classic_rules().token(u64::from(n))
+}
+
+pub fn write_fizzbuzz<W: Write>(out: &mut W, n: u32) -> io::Result<()> {
+    classic_rules().write_fizzbuzz(out, u64::from(n))
+}
 
-    // Al ser esto una expresión, no es necesario `return`
-    lhs % rhs == 0
+pub fn write_fizzbuzz_to<W: Write>(out: &mut W, n: u32) -> io::Result<()> {
+    classic_rules().write_fizzbuzz_to(out, u64::from(n))
 }
 
 // Las funciones que "no" devuelven un valor, en realidad devuelven el tipo de unidad `()`
+// (`clippy` sugiere omitirlo; aquí lo dejamos explícito a modo de ejemplo).
+#[allow(clippy::unused_unit)]
 pub fn fizzbuzz(n: u32) -> () {
-    if is_divisible_by(n, 15) {
-        println!("fizzbuzz");
-    } else if is_divisible_by(n, 3) {
-        println!("fizz");
-    } else if is_divisible_by(n, 5) {
-        println!("buzz");
-    } else {
-        println!("{}", n);
-    }
+    classic_rules().fizzbuzz(u64::from(n));
 }
 
 // Cuando una función devuelve `()`, el tipo de retorno puede
 // omitirse en la firma de la función:
 pub fn fizzbuzz_to(n: u32) {
-    for n in 1..=n {
-        fizzbuzz(n);
+    classic_rules().fizzbuzz_to(u64::from(n));
+}
+
+// Como `fizzbuzz_to`, pero escribiendo los números en otro formato, p.ej.
+// `fizzbuzz_to_with(20, NumberFormat::Roman)`.
+pub fn fizzbuzz_to_with(n: u32, number_format: NumberFormat) {
+    FizzBuzzRules::classic()
+        .with_number_format(number_format)
+        .fizzbuzz_to(u64::from(n));
+}
+
+pub fn write_fizzbuzz_to_with<W: Write>(
+    out: &mut W,
+    n: u32,
+    number_format: NumberFormat,
+) -> io::Result<()> {
+    FizzBuzzRules::classic()
+        .with_number_format(number_format)
+        .write_fizzbuzz_to(out, u64::from(n))
+}
+
+// Iterador perezoso de FizzBuzz.
+// En lugar de imprimir, `FizzBuzz` produce `Token`s tipados sobre cualquier rango
+// (`1..=100`, `10..`, `..50`, ...) con un paso arbitrario. Se puede recorrer al
+// revés (`rev()`) y sabe cuántos elementos le quedan (`remaining`), y se combina con
+// `take_while`, `filter`, `fold`, etc., como cualquier otro iterador.
+//
+// No implementa `ExactSizeIterator`: `0..=u64::MAX` tiene 2^64 elementos, que no
+// caben en un `usize` (por lo mismo, `RangeInclusive<u64>` tampoco lo implementa).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Token {
+    Fizz,
+    Buzz,
+    FizzBuzz,
+    Number(u64),
+}
+
+impl Token {
+    // El token clásico (3 = "fizz", 5 = "buzz") que corresponde a `n`.
+    pub const fn of(n: u64) -> Token {
+        match (is_divisible_by(n, 3), is_divisible_by(n, 5)) {
+            (true, true) => Token::FizzBuzz,
+            (true, false) => Token::Fizz,
+            (false, true) => Token::Buzz,
+            (false, false) => Token::Number(n),
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Fizz => f.write_str("fizz"),
+            Token::Buzz => f.write_str("buzz"),
+            Token::FizzBuzz => f.write_str("fizzbuzz"),
+            Token::Number(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FizzBuzz {
+    // Siguiente número por delante.
+    front: u64,
+    // Números que quedan por producir. Es `u128` porque `0..=u64::MAX`
+    // tiene 2^64 elementos, que no caben en un `u64`.
+    remaining: u128,
+    step: u64,
+}
+
+// Los extremos (incluidos) de un rango de números, o `None` si está vacío.
+// Sin extremo inferior se empieza en `1`, como `fizzbuzz_to`.
+pub(crate) fn inclusive_bounds<R: RangeBounds<u64>>(range: &R) -> Option<(u64, u64)> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => Some(start),
+        Bound::Excluded(&start) => start.checked_add(1),
+        Bound::Unbounded => Some(1),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => Some(end),
+        Bound::Excluded(&end) => end.checked_sub(1),
+        Bound::Unbounded => Some(u64::MAX),
+    };
+
+    match (start, end) {
+        (Some(start), Some(end)) if start <= end => Some((start, end)),
+        _ => None,
+    }
+}
+
+impl FizzBuzz {
+    // Un iterador sobre `range` con paso 1. Si el rango no tiene inicio se
+    // empieza en 1, como el FizzBuzz tradicional; si no tiene fin, en `u64::MAX`.
+    pub fn new<R: RangeBounds<u64>>(range: R) -> FizzBuzz {
+        FizzBuzz::with_step(range, 1)
+    }
+
+    // Igual que `new`, pero avanzando de `step` en `step`.
+    // Igual que `Iterator::step_by`, entra en pánico si `step` es 0.
+    pub fn with_step<R: RangeBounds<u64>>(range: R, step: u64) -> FizzBuzz {
+        assert!(step != 0, "el paso de `FizzBuzz` debe ser mayor que 0");
+
+        match inclusive_bounds(&range) {
+            Some((start, end)) => FizzBuzz {
+                front: start,
+                remaining: u128::from(end - start) / u128::from(step) + 1,
+                step,
+            },
+            // Rango vacío.
+            None => FizzBuzz {
+                front: 0,
+                remaining: 0,
+                step,
+            },
+        }
+    }
+
+    // Cuántos `Token`s quedan por producir (puede ser más que `usize::MAX`).
+    pub fn remaining(&self) -> u128 {
+        self.remaining
+    }
+
+    // El `i`-ésimo número (desde el frente) que aún queda por producir.
+    fn number_at(&self, i: u128) -> u64 {
+        // No se desborda: `i < remaining` garantiza que el valor está dentro del rango.
+        (u128::from(self.front) + i * u128::from(self.step)) as u64
+    }
+}
+
+impl Iterator for FizzBuzz {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.nth(0)
+    }
+
+    // Saltar `n` elementos es O(1): basta con mover el frente.
+    fn nth(&mut self, n: usize) -> Option<Token> {
+        let n = n as u128;
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+
+        let value = self.number_at(n);
+        self.remaining -= n + 1;
+        if self.remaining > 0 {
+            self.front = self.number_at(n + 1);
+        }
+        Some(Token::of(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+
+    // Como `Iterator::count`, entra en pánico si hay más de `usize::MAX` elementos.
+    fn count(self) -> usize {
+        usize::try_from(self.remaining).expect("hay más elementos de los que caben en un `usize`")
+    }
+
+    fn last(mut self) -> Option<Token> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for FizzBuzz {
+    fn next_back(&mut self) -> Option<Token> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Token> {
+        let n = n as u128;
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+
+        self.remaining -= n + 1;
+        Some(Token::of(self.number_at(self.remaining)))
     }
 }
 
+impl FusedIterator for FizzBuzz {}
+
+// Acceso aleatorio a la salida de `fizzbuzz_to`.
+// Para buscar dentro de volcados enormes no hace falta generar las líneas anteriores:
+// basta con contar, por inclusión–exclusión, cuántos números de cada anchura de
+// dígitos se imprimen tal cual y cuántos son sustituidos por palabras.
+
+// El token de la línea `n` (la línea 1 corresponde al número 1).
+pub const fn nth_token(n: u64) -> Token {
+    Token::of(n)
+}
+
+// Cuántos números de `1..=x` no son divisibles ni por 3 ni por 5.
+const fn plain_count_to(x: u64) -> u64 {
+    x - x / 3 - x / 5 + x / 15
+}
+
+// Bytes que ocupan las líneas `1..=m` de la salida (incluidos los saltos de línea).
+const fn output_len_to(m: u64) -> u128 {
+    let m128 = m as u128;
+
+    // Palabras: "fizz" y "buzz" ocupan 4 bytes, "fizzbuzz" 8.
+    let fizzbuzz = m128 / 15;
+    let fizz = m128 / 3 - fizzbuzz;
+    let buzz = m128 / 5 - fizzbuzz;
+    let words = 4 * fizz + 4 * buzz + 8 * fizzbuzz;
+
+    // Números: se agrupan por anchura (1..=9, 10..=99, ...), como mucho 20 grupos.
+    let mut digits = 0;
+    let mut low: u64 = 1;
+    let mut width = 1;
+    while low <= m {
+        // Sin `map_or` ni `min`, que no se pueden usar en una `const fn`.
+        let high = match low.checked_mul(10) {
+            Some(next) if next - 1 < m => next - 1,
+            _ => m,
+        };
+        let plain = plain_count_to(high) - plain_count_to(low - 1);
+        digits += plain as u128 * width;
+
+        if high == m {
+            break;
+        }
+        low = high + 1;
+        width += 1;
+    }
+
+    // Un salto de línea por cada línea.
+    words + digits + m128
+}
+
+// Byte en el que empieza la línea `n` (contando desde 1) de la salida de `fizzbuzz_to`.
+// La línea `0` no existe y se trata como la línea 1.
+pub const fn byte_offset_of_line(n: u64) -> u128 {
+    output_len_to(n.saturating_sub(1))
+}
+
+// Tamaño total, en bytes, de la salida de `fizzbuzz_to(n)`.
+pub const fn fizzbuzz_output_len(n: u64) -> u128 {
+    output_len_to(n)
+}
+
+// La operación inversa: la línea que contiene el byte `offset`. Como
+// `byte_offset_of_line` es creciente, basta una búsqueda binaria (64 pasos como mucho).
+pub fn line_at_byte_offset(offset: u128) -> u64 {
+    let (mut low, mut high) = (1, u64::MAX);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if byte_offset_of_line(mid) <= offset {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
 // Funciones y Métodos asociados
 // VER: https://doc.rust-lang.org/stable/rust-by-example/fn/methods.html
 
@@ -43,63 +416,79 @@ pub fn fizzbuzz_to(n: u32) {
 // asociadas que se llaman en una instancia particular de un tipo.
 // (NOTA: las funciones asociadas equivaldrían en OOP a los métodos estáticos.)
 
-pub struct Point {
-    pub x: f64,
-    pub y: f64,
+// `Point` es genérico sobre el tipo de sus coordenadas (`f64`, `f32`, `i32`, ...,
+// cualquier `Scalar`). El parámetro tiene un valor por defecto, así que `Point` a
+// secas sigue siendo `Point<f64>`.
+//
+// Con `Copy` un punto se pasa por valor, como un número: así los operadores de
+// `geometry` (`p + v`, `q - p`, ...) no consumen sus operandos.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point<T = f64> {
+    pub x: T,
+    pub y: T,
 }
 
 // Bloque de implementación, todas las funciones y métodos asociados a `Point` van aquí.
-impl Point {
+// `impl<T: Scalar>` los implementa para cualquier tipo de coordenada.
+impl<T: Scalar> Point<T> {
     // Esta es una "función asociada" porque esta función está asociada con
     // un tipo particular, es decir, Point.
     //
     // No es necesario llamar a las funciones asociadas con una instancia.
     // Estas funciones se usan generalmente como constructores.
-    pub fn origin() -> Point {
-        Point { x: 0.0, y: 0.0 }
+    pub fn origin() -> Point<T> {
+        Point {
+            x: T::ZERO,
+            y: T::ZERO,
+        }
     }
 
     // Otra función asociada,pero que toma dos argumentos:
-    pub fn new(x: f64, y: f64) -> Point {
+    pub fn new(x: T, y: T) -> Point<T> {
         Point { x, y }
     }
 }
 
-pub struct Rectangle {
-    pub p1: Point,
-    pub p2: Point,
+// Las esquinas pueden venir en cualquier orden (ver `Rectangle::normalized`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rectangle<T = f64> {
+    pub p1: Point<T>,
+    pub p2: Point<T>,
 }
 
-impl Rectangle {
+impl<T: Scalar> Rectangle<T> {
     // Este es un método.
     // `&self` es azúcar sintáctico para `self: &Self`, donde `Self` es el tipo del
     // objeto llamador. En este caso `Self` = `Rectángulo`.
-    pub fn area(&self) -> f64 {
+    //
+    // Con coordenadas enteras, `area`, `perimeter` y `translate` se desbordan como
+    // cualquier suma o producto de enteros; las versiones `checked_*` lo detectan.
+    pub fn area(&self) -> T {
         // `self` da acceso a los campos de estructura a través del operador punto.
         // Usamos un patrón de desestructuración:
         let Point { x: x1, y: y1 } = self.p1;
         let Point { x: x2, y: y2 } = self.p2;
 
-        // `abs` es un método de `f64` que devuelve el valor absoluto del
-        // llamador:
-        ((x1 - x2) * (y1 - y2)).abs()
+        // `abs_diff` es el valor absoluto de la resta: el lado, sea cual sea el
+        // orden de las esquinas.
+        abs_diff(x1, x2) * abs_diff(y1, y2)
     }
 
-    pub fn perimeter(&self) -> f64 {
+    pub fn perimeter(&self) -> T {
         let Point { x: x1, y: y1 } = self.p1;
         let Point { x: x2, y: y2 } = self.p2;
 
-        2.0 * ((x1 - x2).abs() + (y1 - y2).abs())
+        let half = abs_diff(x1, x2) + abs_diff(y1, y2);
+        half + half
     }
 
     // Este método requiere que el objeto que llama sea mutable
     // `&mut self` se convierte en `self: &mut Self`.
-    pub fn translate(&mut self, x: f64, y: f64) {
-        self.p1.x += x;
-        self.p2.x += x;
-
-        self.p1.y += y;
-        self.p2.y += y;
+    pub fn translate(&mut self, x: T, y: T) {
+        // Las dos esquinas se mueven con el mismo vector (ver `geometry`).
+        let offset = Vector2::new(x, y);
+        self.p1 += offset;
+        self.p2 += offset;
     }
 }
 
@@ -113,7 +502,7 @@ impl Pair {
         // Desestructura `self`:
         let Pair(first, second) = self;
 
-        println!("Destruyendo `Pair`: Pair({}, {})", first, second);
+        println!("{}", crate::message!("pair.destroy", first, second));
 
         // `first` y `second` salen fueran del `scope` y su memoria en el montón es liberada.
     }
@@ -225,19 +614,19 @@ pub fn create_fn() -> impl Fn() {
     // VER el error que se produce se el cierre no usa `move` apropiándose de la variable
     // capturada en:
     // file:///home/enrique/DAW/Rust/09-functions/E0373.html
-    move || println!("Esto es un: {}", text)
+    move || println!("{}", crate::message!("closure.this_is", text))
 }
 
 pub fn create_fnmut() -> impl FnMut() {
     let text = "FnMut".to_owned();
 
-    move || println!("Esto es un: {}", text)
+    move || println!("{}", crate::message!("closure.this_is", text))
 }
 
 pub fn create_fnonce() -> impl FnOnce() {
     let text = "FnOnce".to_owned();
 
-    move || println!("Esto es un: {}", text)
+    move || println!("{}", crate::message!("closure.this_is", text))
 }
 
 // Algunos ejemplos del uso de cierres en métodos de la biblioteca standard.
@@ -351,3 +740,83 @@ println!(
     "La suma de los impares hasta 9, excluido: {}",
     sum_odd_numbers(9)
 ); */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_divisible_by_any_integer() {
+        assert!(15u32.is_divisible_by(5));
+        assert!(!16u32.is_divisible_by(5));
+        assert!((-12i32).is_divisible_by(4));
+        assert!(i64::MIN.is_divisible_by(-1));
+        assert!(!7u8.is_divisible_by(0));
+        assert_eq!(checked_is_divisible_by(7u8, 0), Err(DivisionByZero));
+
+        // Se puede evaluar al compilar.
+        const { assert!(is_divisible_by(15, 15) && !is_divisible_by(15, 0)) };
+        for (lhs, rhs) in [
+            (0, 0),
+            (0, 3),
+            (9, 3),
+            (10, 3),
+            (u64::MAX, 5),
+            (u64::MAX, 0),
+        ] {
+            assert_eq!(
+                is_divisible_by(lhs, rhs),
+                Divisible::is_divisible_by(lhs, rhs)
+            );
+        }
+    }
+
+    #[test]
+    fn fizzbuzz_iterator_size() {
+        let iter = FizzBuzz::with_step(1..=30, 7);
+        assert_eq!(iter.remaining(), 5);
+        assert_eq!(iter.size_hint(), (5, Some(5)));
+        assert_eq!(iter.clone().count(), 5);
+
+        // 2^64 elementos: más de los que caben en un `usize` de 64 bits.
+        let mut iter = FizzBuzz::new(0..=u64::MAX);
+        assert_eq!(iter.remaining(), 1 << 64);
+        assert_eq!(iter.size_hint(), (usize::MAX, None));
+        assert_eq!(iter.next_back(), Some(Token::of(u64::MAX)));
+        assert_eq!(iter.remaining(), u128::from(u64::MAX));
+        assert_eq!(FizzBuzz::new(5..5).remaining(), 0);
+    }
+
+    #[test]
+    fn nth_token_matches_fizzbuzz_str() {
+        for n in 1..=1_000 {
+            assert_eq!(nth_token(u64::from(n)).to_string(), fizzbuzz_str(n));
+        }
+    }
+
+    #[test]
+    fn byte_offset_of_line_matches_output() {
+        let mut out = Vec::new();
+        write_fizzbuzz_to(&mut out, 20_000).unwrap();
+
+        // Cada línea empieza justo después del salto de línea anterior.
+        let mut offset = 0;
+        for (i, line) in out.split_inclusive(|&b| b == b'\n').enumerate() {
+            let n = i as u64 + 1;
+            assert_eq!(byte_offset_of_line(n), offset, "línea {n}");
+            assert_eq!(line_at_byte_offset(offset), n);
+            assert_eq!(line_at_byte_offset(offset + line.len() as u128 - 1), n);
+            offset += line.len() as u128;
+        }
+        assert_eq!(fizzbuzz_output_len(20_000), out.len() as u128);
+        assert_eq!(byte_offset_of_line(0), 0);
+    }
+
+    #[test]
+    fn output_len_at_top_of_u64_range() {
+        // La última línea ocupa lo que su token más el salto de línea.
+        let last = fizzbuzz_output_len(u64::MAX) - byte_offset_of_line(u64::MAX);
+        assert_eq!(last, nth_token(u64::MAX).to_string().len() as u128 + 1);
+        assert_eq!(line_at_byte_offset(fizzbuzz_output_len(u64::MAX)), u64::MAX);
+    }
+}
@@ -1,11 +1,506 @@
 // #![feature(never_type)]
+// Los ejemplos usan a propósito `vec!`, `iter().any()` y `fold` para ilustrar
+// los cierres, aunque `clippy` sugiera alternativas más cortas:
+#![allow(clippy::useless_vec, clippy::manual_contains, clippy::unnecessary_fold)]
 
 use functions::{
-    create_fn, create_fnmut, create_fnonce, fizzbuzz_to, is_odd, Pair, Point, Rectangle,
+    contains_digit, create_fn, create_fnmut, create_fnonce, fizzbuzz_output_len, fizzbuzz_static,
+    fizzbuzz_table, fizzbuzz_to, is_divisible_by, is_odd, is_prime, message, parse_rules,
+    set_locale, verify, write_fizzbuzz_fast, write_fizzbuzz_parallel, write_fizzbuzz_resumable,
+    write_fizzbuzz_to, BigFizzBuzz, Bot, Circle, Divisor, Escalation, FizzBuzz, FizzBuzzRules,
+    Game, GameEvent, OutputFormat, Pair, ParallelConfig, Player, Point, Rectangle, ResumeOptions,
+    Rule, RuleMode, Scene, Shape, Token, Transform2D, Triangle, VerifyOptions,
 };
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::hint::black_box;
+use std::io::{self, BufRead, Write};
 use std::mem;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Sin argumentos se ejecutan los ejemplos del capítulo; además hay varios subcomandos.
+// Con `--lang es|en` los mensajes se muestran en español (por defecto) o en inglés.
+// • `fizzbuzz [n] [--rules <fichero>] [--numbers <formato>] [--format text|json|ndjson|csv]
+//   [--output <fichero> [--resume] [--checkpoint-every <líneas>]]`:
+//   escribe FizzBuzz de 1 a `n` (100 por defecto) con las reglas clásicas o con las
+//   descritas en el fichero (ver `parse_rules`), como texto o como registros
+//   estructurados. `--numbers` indica cómo escribir los números (ver `NumberFormat`).
+//   Con `--output` se escribe en un fichero con puntos de control cada
+//   `--checkpoint-every` líneas (un millón por defecto), y `--resume` continúa donde
+//   se quedó si se interrumpió (ver `write_fizzbuzz_resumable`).
+// • `verify [fichero] [--n <n>] [--rules <fichero>] [--numbers <formato>]
+//   [--strictness strict|normal|lenient] [--context <líneas>]`: comprueba una salida
+//   de FizzBuzz (de `stdin` si no se indica fichero) e informa del primer error.
+// • `stream <n> [--threads <t>] [--chunk <c>] [--in-flight <f>]`: escribe FizzBuzz
+//   de 1 a `n` en `stdout` con el generador rápido (en paralelo si se indica alguna de
+//   las opciones; sin `--threads`, con tantos hilos como núcleos).
+// • `big <inicio> [cantidad]`: escribe `cantidad` (100 por defecto) líneas de FizzBuzz
+//   a partir de `inicio`, un número decimal de cualquier tamaño (p.ej. 10^40).
+// • `bench [n] [--divisors]`: mide el rendimiento del generador rápido frente a
+//   `write_fizzbuzz_to`, o (con `--divisors`) el de `Divisor::divides` frente a
+//   `is_divisible_by`.
+// • `game [--players <nombres>] [--bots <n>] [--error-rate <p>] [--delay <ms>]
+//   [--escalate <rondas>] [--seed <s>] [--rules <fichero>] [--numbers <formato>]`:
+//   partida por turnos en la terminal entre los humanos de `--players` (separados
+//   por comas) y `--bots` jugadores simulados (3 por defecto). Cada `--escalate`
+//   rondas (3 por defecto, `0` para nunca) se añade una regla nueva.
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if let Err(err) = set_lang(&mut args) {
+        eprintln!("{}", message!("error", err));
+        return ExitCode::FAILURE;
+    }
+    if args.is_empty() {
+        demo();
+        return ExitCode::SUCCESS;
+    }
+
+    let command = args.remove(0);
+    let result = match command.as_str() {
+        "fizzbuzz" => fizzbuzz(args),
+        "verify" => verify_output(args),
+        "stream" => stream(args),
+        "big" => big(args),
+        "bench" => bench(args),
+        "game" => game(args),
+        other => Err(message!("error.unknown_command", other).into()),
+    };
+
+    // Mostramos el error con `Display` (y no con `Debug`, como haría `main` al devolver `Result`).
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", message!("error", err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Aplica `--lang es|en` (en cualquier posición) a todos los mensajes.
+fn set_lang(args: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+    if let Some(lang) = take_option(args, "--lang")? {
+        set_locale(lang.parse()?);
+    }
+    Ok(())
+}
+
+// Convierte un argumento en un número, con un mensaje de error legible.
+fn parse_number<T>(arg: &str) -> Result<T, Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    arg.parse()
+        .map_err(|err| message!("error.invalid_number", arg, err).into())
+}
+
+// Extrae de `args` la opción `--name <valor>`, si aparece, y devuelve su valor.
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>, Box<dyn Error>> {
+    match args.iter().position(|arg| arg == name) {
+        None => Ok(None),
+        Some(i) if i + 1 < args.len() => {
+            let value = args.remove(i + 1);
+            args.remove(i);
+            Ok(Some(value))
+        }
+        Some(_) => Err(message!("error.missing_value", name).into()),
+    }
+}
+
+// Quita de `args` la opción sin valor `--name` y dice si aparecía.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|arg| arg == name) {
+        None => false,
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+    }
+}
+
+// Lee el argumento numérico `n` o usa `default` si no se ha indicado.
+fn parse_n(args: &[String], default: Option<u64>) -> Result<u64, Box<dyn Error>> {
+    match (args.first(), default) {
+        (Some(arg), _) => parse_number(arg),
+        (None, Some(default)) => Ok(default),
+        (None, None) => Err(message!("error.missing_n").into()),
+    }
+}
+
+// Cerrar la tubería (`| head`) no es un error para quien escribe en `stdout`.
+fn ignore_broken_pipe(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+// Lee las reglas de `--rules <fichero>`, o las clásicas si no se indica la opción,
+// y les aplica `--numbers <formato>`, si se indica.
+fn load_rules(args: &mut Vec<String>) -> Result<FizzBuzzRules, Box<dyn Error>> {
+    let rules = read_rules(args)?;
+    match take_option(args, "--numbers")? {
+        Some(numbers) => Ok(rules.with_number_format(numbers.parse()?)),
+        None => Ok(rules),
+    }
+}
+
+fn read_rules(args: &mut Vec<String>) -> Result<FizzBuzzRules, Box<dyn Error>> {
+    match take_option(args, "--rules")? {
+        None => Ok(FizzBuzzRules::classic()),
+        Some(path) => {
+            let src =
+                fs::read_to_string(&path).map_err(|err| message!("error.read_file", path, err))?;
+            parse_rules(&src).map_err(|err| message!("error.rules_file", path, err).into())
+        }
+    }
+}
+
+fn fizzbuzz(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let rules = load_rules(&mut args)?;
+    let format = match take_option(&mut args, "--format")? {
+        Some(format) => format.parse()?,
+        None => OutputFormat::Text,
+    };
+    let output = take_option(&mut args, "--output")?;
+    let mut options = ResumeOptions {
+        resume: take_flag(&mut args, "--resume"),
+        ..ResumeOptions::default()
+    };
+    if let Some(every) = take_option(&mut args, "--checkpoint-every")? {
+        options.checkpoint_every = parse_number(&every)?;
+    }
+    let n = parse_n(&args, Some(100))?;
+
+    if let Some(path) = output {
+        if format != OutputFormat::Text {
+            return Err(message!("error.output_format").into());
+        }
+        let report = write_fizzbuzz_resumable(&path, &rules, n, &options)?;
+        if let Some(resumed) = report.resumed {
+            println!(
+                "{}",
+                message!("resume.resumed", resumed.last_n, resumed.byte_offset)
+            );
+        }
+        let checkpoint = report.checkpoint;
+        println!(
+            "{}",
+            message!(
+                "resume.done",
+                path,
+                checkpoint.last_n,
+                checkpoint.byte_offset
+            )
+        );
+        return Ok(());
+    }
+    if options.resume {
+        return Err(message!("error.resume_needs_output").into());
+    }
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    ignore_broken_pipe(rules.write_formatted_to(&mut out, n, format))?;
+    Ok(())
+}
+
+fn verify_output(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let rules = load_rules(&mut args)?;
+    let mut options = VerifyOptions::default();
+    if let Some(n) = take_option(&mut args, "--n")? {
+        options.n = Some(parse_number(&n)?);
+    }
+    if let Some(strictness) = take_option(&mut args, "--strictness")? {
+        options.strictness = strictness.parse()?;
+    }
+    if let Some(context) = take_option(&mut args, "--context")? {
+        options.context = parse_number(&context)?;
+    }
+
+    let report = match args.first().map(String::as_str) {
+        None | Some("-") => verify(io::stdin().lock(), &rules, &options)?,
+        Some(path) => {
+            let file =
+                fs::File::open(path).map_err(|err| message!("error.open_file", path, err))?;
+            verify(io::BufReader::new(file), &rules, &options)?
+        }
+    };
+
+    match report.mismatch {
+        None => {
+            println!("{}", message!("verify.ok", report.lines));
+            Ok(())
+        }
+        Some(mismatch) => Err(mismatch.to_string().into()),
+    }
+}
+
+fn stream(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let threads = take_option(&mut args, "--threads")?;
+    let chunk = take_option(&mut args, "--chunk")?;
+    let in_flight = take_option(&mut args, "--in-flight")?;
+    let n = parse_n(&args, None)?;
+
+    let mut out = io::stdout().lock();
+    let result = if threads.is_none() && chunk.is_none() && in_flight.is_none() {
+        write_fizzbuzz_fast(&mut out, n)
+    } else {
+        // Lo que no se indique se queda como en `ParallelConfig::default()`.
+        let mut config = ParallelConfig::default();
+        if let Some(threads) = threads {
+            config.threads = parse_number(&threads)?;
+            config.max_in_flight = 2 * config.threads;
+        }
+        if let Some(chunk) = chunk {
+            config.chunk_len = parse_number(&chunk)?;
+        }
+        if let Some(in_flight) = in_flight {
+            config.max_in_flight = parse_number(&in_flight)?;
+        }
+        write_fizzbuzz_parallel(&mut out, n, &config)
+    };
+    ignore_broken_pipe(result)?;
+    Ok(())
+}
+
+fn big(args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let start = match args.first() {
+        Some(start) => BigFizzBuzz::from_decimal(start)?,
+        None => return Err(message!("error.missing_start").into()),
+    };
+    let count = match args.get(1) {
+        Some(count) => parse_number(count)?,
+        None => 100,
+    };
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let result = start
+        .take(count)
+        .try_for_each(|token| writeln!(out, "{}", token))
+        .and_then(|()| out.flush());
+    ignore_broken_pipe(result)?;
+    Ok(())
+}
+
+fn bench(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    if take_flag(&mut args, "--divisors") {
+        return bench_divisors(args);
+    }
+    let n = parse_n(&args, Some(100_000_000))?;
+    let bytes = fizzbuzz_output_len(n) as f64;
+
+    // Escribimos en `io::sink()` para medir solo la generación, no el terminal.
+    let start = Instant::now();
+    write_fizzbuzz_fast(&mut io::sink(), n)?;
+    let fast = start.elapsed().as_secs_f64();
+
+    let config = ParallelConfig::default();
+    let start = Instant::now();
+    write_fizzbuzz_parallel(&mut io::sink(), n, &config)?;
+    let parallel = start.elapsed().as_secs_f64();
+
+    // La versión con `writeln!` por línea es mucho más lenta: la medimos
+    // con menos números y extrapolamos por bytes.
+    let slow_n = n.min(10_000_000) as u32;
+    let slow_bytes = fizzbuzz_output_len(u64::from(slow_n)) as f64;
+    let start = Instant::now();
+    write_fizzbuzz_to(&mut io::BufWriter::new(io::sink()), slow_n)?;
+    let slow = start.elapsed().as_secs_f64();
+
+    let mut out = io::stdout().lock();
+    writeln!(out, "{}", message!("bench.header", n, bytes))?;
+    let rows = [
+        (message!("bench.fast"), fast, bytes),
+        (message!("bench.parallel", config.threads), parallel, bytes),
+        (
+            format!("`write_fizzbuzz_to({})`:", slow_n),
+            slow,
+            slow_bytes,
+        ),
+    ];
+    for (label, seconds, bytes) in rows {
+        writeln!(
+            out,
+            "  {:<32} {:>8.3} s {:>8.3} GB/s",
+            label,
+            seconds,
+            bytes / seconds / 1e9
+        )?;
+    }
+    Ok(())
+}
+
+// Cuenta los números de `1..=n` divisibles por 3, 5 y 15 de dos formas. Los divisores
+// pasan por `black_box` para que el compilador no los trate como constantes (y
+// sustituya él mismo la división por una multiplicación).
+fn bench_divisors(args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let n = parse_n(&args, Some(100_000_000))?;
+    let divisors = black_box([3u64, 5, 15]);
+
+    let start = Instant::now();
+    let mut remainder = [0u64; 3];
+    for i in 1..=n {
+        let i = black_box(i);
+        for (count, &divisor) in remainder.iter_mut().zip(&divisors) {
+            *count += u64::from(is_divisible_by(i, divisor));
+        }
+    }
+    let remainder_secs = start.elapsed().as_secs_f64();
+
+    let precomputed = divisors.map(Divisor::new);
+    let start = Instant::now();
+    let mut inverse = [0u64; 3];
+    for i in 1..=n {
+        let i = black_box(i);
+        for (count, divisor) in inverse.iter_mut().zip(&precomputed) {
+            *count += u64::from(divisor.divides(i));
+        }
+    }
+    let inverse_secs = start.elapsed().as_secs_f64();
+
+    if remainder != inverse {
+        return Err(message!(
+            "error.bench_mismatch",
+            format!("{:?}", remainder),
+            format!("{:?}", inverse)
+        )
+        .into());
+    }
+
+    let mut out = io::stdout().lock();
+    writeln!(
+        out,
+        "{}",
+        message!(
+            "bench.divisors.header",
+            format!("{:?}", divisors),
+            n,
+            format!("{:?}", inverse)
+        )
+    )?;
+    for (label, seconds) in [
+        (message!("bench.divisors.remainder"), remainder_secs),
+        (message!("bench.divisors.inverse"), inverse_secs),
+    ] {
+        writeln!(
+            out,
+            "  {:<32} {:>8.3} s {:>8.1} {}",
+            label,
+            seconds,
+            n as f64 / seconds / 1e6,
+            message!("bench.numbers_per_second")
+        )?;
+    }
+    Ok(())
+}
+
+fn game(mut args: Vec<String>) -> Result<(), Box<dyn Error>> {
+    let rules = load_rules(&mut args)?;
+    let names =
+        take_option(&mut args, "--players")?.unwrap_or_else(|| message!("game.default_player"));
+    let bots = match take_option(&mut args, "--bots")? {
+        Some(bots) => parse_number(&bots)?,
+        None => 3,
+    };
+    let mut bot = Bot::default();
+    if let Some(error_rate) = take_option(&mut args, "--error-rate")? {
+        bot.error_rate = parse_number(&error_rate)?;
+        if !(0.0..=1.0).contains(&bot.error_rate) {
+            return Err(message!("error.error_rate").into());
+        }
+    }
+    if let Some(delay) = take_option(&mut args, "--delay")? {
+        bot.max_delay = Duration::from_millis(parse_number(&delay)?);
+        bot.min_delay = bot.min_delay.min(bot.max_delay);
+    }
+    let mut escalation = Escalation::default();
+    if let Some(every) = take_option(&mut args, "--escalate")? {
+        escalation.every_rounds = parse_number(&every)?;
+    }
+    // Sin `--seed`, cada partida es distinta; se muestra la semilla para poder repetirla.
+    let seed = match take_option(&mut args, "--seed")? {
+        Some(seed) => parse_number(&seed)?,
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64),
+    };
+
+    let mut players: Vec<Player> = names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(Player::human)
+        .collect();
+    players.extend((1..=bots).map(|i| Player::bot(message!("game.bot_name", i), bot.clone())));
+    if players.is_empty() {
+        return Err(message!("error.no_players").into());
+    }
+
+    let mut game = Game::with_rules(players, rules, seed).with_escalation(escalation);
+    let names: Vec<&str> = game.players().iter().map(|p| p.name.as_str()).collect();
+    println!("{}", message!("game.start", names.join(", "), seed));
+
+    let mut lines = io::stdin().lock().lines();
+    while let Some(current) = game.current_player() {
+        let name = game.players()[current].name.clone();
+        let answer = match game.bot_move() {
+            Some(bot_move) => {
+                thread::sleep(bot_move.delay);
+                println!("{}", message!("game.says", name, bot_move.answer));
+                bot_move.answer
+            }
+            None => {
+                print!("{}", message!("game.prompt", name, game.number()));
+                io::stdout().flush()?;
+                match lines.next() {
+                    Some(line) => line?,
+                    None => {
+                        println!();
+                        println!("{}", message!("game.abandoned"));
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        for event in game.submit(&answer) {
+            match event {
+                GameEvent::Correct { .. } => {}
+                GameEvent::Eliminated {
+                    player,
+                    expected,
+                    answer,
+                    ..
+                } => {
+                    let name = &game.players()[player].name;
+                    println!("{}", message!("game.eliminated", name, expected, answer));
+                }
+                GameEvent::RuleAdded {
+                    round,
+                    divisor,
+                    word,
+                } => println!("{}", message!("game.rule_added", round, divisor, word)),
+                GameEvent::GameOver {
+                    winner: Some(winner),
+                } => {
+                    let winner = &game.players()[winner];
+                    println!("{}", message!("game.winner", winner.name, winner.score));
+                }
+                GameEvent::GameOver { winner: None } => {
+                    println!("{}", message!("game.no_winner", game.number()));
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
-fn main() {
+fn demo() {
     // 09-Funciones.
     // =============================================================
     // VER: https://doc.rust-lang.org/stable/rust-by-example/fn.html
@@ -34,8 +529,75 @@ fn main() {
     // Los métodos se llaman usando el operador punto
     // Tenga en cuenta que el primer argumento `self` se pasa implícitamente,
     // es decir, `rectangle.perimeter()` === `Rectangle::perimeter(rectangle)`
-    println!("Perímetro del rectángulo: {}", rectangle.perimeter());
-    println!("Área del rectángulo: {}", rectangle.area());
+    println!(
+        "{}",
+        message!("demo.rectangle.perimeter", rectangle.perimeter())
+    );
+    println!("{}", message!("demo.rectangle.area", rectangle.area()));
+    println!(
+        "{}",
+        message!(
+            "demo.rectangle.diagonal",
+            rectangle.p1.distance(rectangle.p2)
+        )
+    );
+
+    // `Rectangle` es genérico: con coordenadas enteras, `checked_area` detecta el
+    // desbordamiento (70000 × 70000 no cabe en un `i32`).
+    let pixels: Rectangle<i32> = Rectangle::new(Point::origin(), Point::new(70_000, 70_000));
+    let area = pixels
+        .checked_area()
+        .map_or_else(|err| err.to_string(), |area| area.to_string());
+    println!("{}", message!("demo.rectangle.checked_area", area));
+
+    // Figuras de distintos tipos en la misma escena (`Vec<Box<dyn Shape>>`):
+    let scene = Scene::new()
+        .with(Rectangle::new(Point::origin(), Point::new(4.0, 3.0)))
+        .with(Circle::new(Point::new(4.0, 3.0), 1.0))
+        .with(Triangle::new(
+            Point::new(10.0, 0.0),
+            Point::new(12.0, 0.0),
+            Point::new(11.0, 2.0),
+        ));
+    let report = scene.report(200);
+    println!(
+        "{}",
+        message!(
+            "demo.scene.total",
+            scene.shapes.len(),
+            format!("{:.2}", report.total_area),
+            format!("{:.2}", report.covered_area)
+        )
+    );
+    for overlap in &report.overlaps {
+        println!(
+            "{}",
+            message!(
+                "demo.scene.overlap",
+                overlap.first,
+                overlap.second,
+                format!("{:.2}", overlap.area)
+            )
+        );
+    }
+
+    // Al girar un rectángulo sus lados dejan de ser paralelos a los ejes: el resultado
+    // es un polígono con la misma área, no un rectángulo más grande.
+    let rectangle43 = Rectangle::new(Point::origin(), Point::new(4.0, 3.0));
+    let rotated = rectangle43.transformed(&Transform2D::rotate_around(
+        rectangle43.center(),
+        std::f64::consts::FRAC_PI_4,
+    ));
+    let bounds = rotated.bounding_box();
+    println!(
+        "{}",
+        message!(
+            "demo.transform.rotated",
+            format!("{:.2}", rotated.area()),
+            format!("{:.2}", bounds.width()),
+            format!("{:.2}", bounds.height())
+        )
+    );
 
     // Si tratamos de usar la varible `rectangle`, que no ha sido declarada como mutable,
     // se producirá un error: `¡no se puede prestar «rectangle» como mutable!`
@@ -95,14 +657,17 @@ fn main() {
     let i = 1;
 
     // Llamando las funciones y a los `closures`:
-    println!("`function`: {}", function(i));
-    println!("`closure_annotated`: {}", closure_annotated(i));
-    println!("`closure_inferred`: {}", closure_inferred(i));
+    println!("{}", message!("demo.function", function(i)));
+    println!(
+        "{}",
+        message!("demo.closure_annotated", closure_annotated(i))
+    );
+    println!("{}", message!("demo.closure_inferred", closure_inferred(i)));
 
     // Un cierre sin argumentos que devuelve un `i32`.
     // Se infiere automáticamente el tipo de retorno.
     let one = || 1;
-    println!("`Closure` que devuelve `1`: {}", one());
+    println!("{}", message!("demo.closure_one", one()));
 
     // `Closures`: capturando los valores del entorno.
     // VER: https://doc.rust-lang.org/stable/rust-by-example/fn/closures/capture.html
@@ -125,7 +690,7 @@ fn main() {
     //
     // `println!` solo requiere argumentos por referencia inmutable, por lo que no
     // se imponen una fuerte restricción:
-    let print = || println!("`color` es: {}", color);
+    let print = || println!("{}", message!("demo.color", color));
 
     // Llamando al `closure` usando el préstamo:
     print();
@@ -148,7 +713,7 @@ fn main() {
     // llamar al cierre muta el cierre que requiere un `mut`.
     let mut inc = || {
         count += 1;
-        println!("`count`: {}", count);
+        println!("{}", message!("demo.count", count));
     };
 
     // Llamando a `closure` usando el préstamo mutable:
@@ -175,7 +740,7 @@ fn main() {
     // copiaría en el cierre dejando intacto el original.
     // Un tipo no copiable debe moverse, por lo que `movable` se mueve inmediatamente al cierre.
     let consume = || {
-        println!("`movable`: {:?}", movable);
+        println!("{}", message!("demo.movable", format!("{:?}", movable)));
         mem::drop(movable);
     };
 
@@ -258,12 +823,12 @@ fn main() {
     // `farewell` por valor.
     let diary = || {
         // `greeting` es tomado por referencia: requiere `Fn`:
-        println!("Dije {}.", greeting);
+        println!("{}", message!("demo.said", greeting));
         // La mutación obliga a `farewell` a ser capturado por
         // referencia mutable. Ahora requiere `FnMut`.
         farewell.push_str("!!!");
-        println!("Luego grité {}.", farewell);
-        println!("Ahora puedo dormir. zzzzz");
+        println!("{}", message!("demo.screamed", farewell));
+        println!("{}", message!("demo.sleep"));
 
         // Llamamos manualmente a `drop` por lo que forzamos a que `farewell`
         // sea capturado por valor. Ahora requiere `FnOnce`.
@@ -287,11 +852,11 @@ fn main() {
     // `double` satisface el límite de rasgo que requiere `apply_to_3`:
     let double = |x| 2 * x;
 
-    println!("El doble de 3 es: {}", apply_to_3(double));
+    println!("{}", message!("demo.double", apply_to_3(double)));
     // `apply_to_3` toma un cierre que implementa `Fn`, por lo que solo requiere referencias
     // inmutables. Graciias a la coerción de `deref`, si le pasamos un `i32`, fuerza un `&i32`,
     // por lo que podemos llamar a `apply_to_3` otra vez:
-    println!("El doble de 3 es: {}", apply_to_3(double));
+    println!("{}", message!("demo.double", apply_to_3(double)));
 
     // `Closures` y Tipos Anónimos.
     // VER: https://doc.rust-lang.org/stable/rust-by-example/fn/closures/anonymity.html
@@ -349,15 +914,30 @@ fn main() {
 
     // Definir una función contenedora que satisfaga el límite `Fn`:
     fn function1() {
-        println!("¡Soy una función!");
+        println!("{}", message!("demo.i_am_function"));
     }
 
     // Definir un cierre que también satisfaga el límite `Fn`:
-    let closure = || println!("¡Soy un cierre!");
+    let closure = || println!("{}", message!("demo.i_am_closure"));
 
     call_me(closure);
     call_me(function1);
 
+    // Las reglas de `FizzBuzzRules` también aceptan cierres (y funciones) como entrada,
+    // igual que `apply` o `call_me`. Aquí mezclamos reglas de divisibilidad con:
+    // • una función que cumple el límite `Fn(u64) -> bool` (`is_prime`),
+    // • un cierre que captura por valor (`move`) el dígito que buscamos.
+    let digit = 3;
+    let rules = FizzBuzzRules::classic()
+        .with(Rule::when(move |n| contains_digit(n, digit), "three").with_priority(-1))
+        .with(
+            Rule::when(is_prime, "prime")
+                .with_priority(1)
+                .with_mode(RuleMode::Override),
+        );
+    let tokens: Vec<_> = (21..=40).map(|n| rules.token(n).into_owned()).collect();
+    println!("{}", message!("demo.closure_rules", tokens.join(", ")));
+
     // `Closures` como parámetros de salida.
     // VER: https://doc.rust-lang.org/stable/rust-by-example/fn/closures/output_parameters.html
 
@@ -431,24 +1011,33 @@ fn main() {
     // se puede comparar una referencia con un primitivo, así que hacemos una desestructuración con
     // `|&x| x ==2` o hacemos una desreferenciación con `|x| == *x == 2`
     // VER: https://stackoverflow.com/questions/34733811/what-is-the-difference-between-iter-and-into-iter
-    println!("¿Está `2` en `vec1`: {}", vec1.iter().any(|&x| x == 2));
+    println!(
+        "{}",
+        message!("demo.any", "vec1", vec1.iter().any(|&x| x == 2))
+    );
     // `into_iter()` para vecs produce `i32`. No requiere desestructuración.
     // Hay que tener en cuenta que `into_iter()` no entrega referencias sino los valores,
     // lo que significa que mueve el vector (VER: https://doc.rust-lang.org/std/vec/struct.IntoIter.html)
     // al `struct IntoIter`, a diferencia de `iter()` que si entraga un `struct` de referencias
     // (VER: https://doc.rust-lang.org/std/slice/struct.Iter.html);
     // esto quiere decir que en el primer caso no podemos volver a usar el vector.
-    println!("¿Está `2` en `vec2`: {}", vec2.into_iter().any(|x| x == 2));
+    println!(
+        "{}",
+        message!("demo.any", "vec2", vec2.into_iter().any(|x| x == 2))
+    );
     // ¡Error! Prestando un valor que ha sido movido por el método `into_iter()`
     // println!("Usando `vec2` de nuevo: {:?}", vec2);
 
     // Lo mismo es aplicable para arrays:
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
-    println!("¿Está `2` en `array1`: {}", array1.iter().any(|&x| x == 2));
     println!(
-        "¿Está `2` en `array2`: {}",
-        array2.into_iter().any(|x| x == 2)
+        "{}",
+        message!("demo.any", "array1", array1.iter().any(|&x| x == 2))
+    );
+    println!(
+        "{}",
+        message!("demo.any", "array2", array2.into_iter().any(|x| x == 2))
     );
 
     // Buscando a través de iteradores: El caso del método `find` del rasgo `Iterator`
@@ -488,26 +1077,45 @@ fn main() {
     // podemos comparar con un primitivo `i32`. Por ello nos vemos obligados a desectructurar
     // un doble ampersand (`&&`).
     // Se podría hacer lo mismo con una doble desreferenciación: `|x| **x == 2`
-    println!("Encontrado `2` en `vec1`: {:?}", iter.find(|&&x| x == 2));
+    println!(
+        "{}",
+        message!(
+            "demo.find",
+            "vec1",
+            format!("{:?}", iter.find(|&&x| x == 2))
+        )
+    );
     // `into_iter()` para vecs produce `i32`, y queremos hacer referencia a uno de
     // sus elementos, por lo que tenemos que desestructurar `&i32` a `i32`.
     // En el caso de `into_iter()`, basta un solo ampersand para hacer la desectructuración,
     // o una simple desreferenciación: `|x| *x ==2`
     println!(
-        "Encontrado `2` en `vec2`: {:?}",
-        into_iter.find(|&x| x == 2)
+        "{}",
+        message!(
+            "demo.find",
+            "vec2",
+            format!("{:?}", into_iter.find(|&x| x == 2))
+        )
     );
 
     // Lo mismo es aplicable para arrays:
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
     println!(
-        "¿Encontrado `2` en `array1`: {:?}",
-        array1.iter().find(|&&x| x == 2)
+        "{}",
+        message!(
+            "demo.find",
+            "array1",
+            format!("{:?}", array1.iter().find(|&&x| x == 2))
+        )
     );
     println!(
-        "¿Está `2` en `array2`: {:?}",
-        array2.into_iter().find(|&x| x == 2)
+        "{}",
+        message!(
+            "demo.find",
+            "array2",
+            format!("{:?}", array2.into_iter().find(|&x| x == 2))
+        )
     );
 
     // Iterator::find te da una referencia al item. Pero si deseas el índice del item, usa Iterator::position.
@@ -519,16 +1127,19 @@ fn main() {
     // por lo que tenemos que desestructurar `&i32` a `i32`.
     let index_of_first_even_number = vec.iter().position(|&x| x % 2 == 0);
     println!(
-        "Índice del primer número par: {}",
-        index_of_first_even_number.unwrap()
+        "{}",
+        message!("demo.first_even", index_of_first_even_number.unwrap())
     );
 
     // `into_iter()` para vecs produce `i32` y `position()` no toma una referencia, entonces
     // no tenemos que desestructurar.
     let index_of_first_negative_number = vec.into_iter().position(|x| x < 0);
     println!(
-        "Índice de primer número negativo: {:?}",
-        index_of_first_negative_number
+        "{}",
+        message!(
+            "demo.first_negative",
+            format!("{:?}", index_of_first_negative_number)
+        )
     );
 
     // Rust proporciona funciones de orden superior (HOF).
@@ -536,7 +1147,7 @@ fn main() {
     // una función más útil. Los HOF y los iteradores perezosos le dan a
     // Rust su sabor funcional.
 
-    println!("Encuentra la suma de todos los números impares al cuadrado menores de 1000.");
+    println!("{}", message!("demo.squared_odd"));
 
     let upper = 1000;
 
@@ -556,7 +1167,7 @@ fn main() {
         }
     }
 
-    println!("Resultado obtenido en estilo imperativo: {}", acc);
+    println!("{}", message!("demo.imperative", acc));
 
     // *** Aproximación funcional ***
     // VER las definiciones de los métodos que se usan seguidamente:
@@ -571,8 +1182,35 @@ fn main() {
         .fold(0, |acc, n_squared| acc + n_squared); // Acumúlalos mediante un cierre acumulador
 
     println!(
-        "Resultado obtenido en estilo funcional: {}",
-        sum_of_squared_odd_numbers
+        "{}",
+        message!("demo.functional", sum_of_squared_odd_numbers)
+    );
+
+    // El iterador `FizzBuzz` es perezoso, así que se combina igual que `(0..)`:
+    // sumamos los números que no son ni `fizz` ni `buzz` por debajo de 100.
+    let sum_of_plain_numbers: u64 = FizzBuzz::new(1..)
+        .take_while(|&token| token != Token::Number(101)) // `101` es el primer número mayor que 100
+        .filter_map(|token| match token {
+            Token::Number(n) => Some(n),
+            _ => None,
+        })
+        .fold(0, |acc, n| acc + n);
+
+    println!("{}", message!("demo.plain_sum", sum_of_plain_numbers));
+
+    // También puede recorrerse al revés y con un paso arbitrario:
+    let countdown: Vec<String> = FizzBuzz::with_step(1..=30, 7)
+        .rev()
+        .map(|token| token.to_string())
+        .collect();
+    println!("{}", message!("demo.countdown", countdown.join(", ")));
+
+    // O calcularse entera al compilar, sin coste al ejecutar el programa:
+    const TABLE: [Token; 15] = fizzbuzz_table();
+    const OUTPUT: &str = fizzbuzz_static!(15);
+    println!(
+        "{}",
+        message!("demo.const_table", TABLE[14], OUTPUT.lines().count())
     );
 
     // Funciones Divergentes.
@@ -625,8 +1263,5 @@ fn main() {
         acc
     }
 
-    println!(
-        "La suma de los impares hasta 9, excluido: {}",
-        sum_odd_numbers(9)
-    );
+    println!("{}", message!("demo.odd_sum", sum_odd_numbers(9)));
 }
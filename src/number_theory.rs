@@ -0,0 +1,618 @@
+// Teoría de números alrededor de `is_divisible_by`.
+// =============================================================
+// Máximo común divisor, mínimo común múltiplo, Euclides extendido, inverso modular,
+// divisores, factorización en primos (división por tentativa y rho de Pollard),
+// test de primalidad de Miller–Rabin, función φ de Euler y una criba segmentada.
+//
+// Todo es genérico sobre los mismos enteros que `Divisible` (de `u8` a `u128` y de
+// `i8` a `i128`). Internamente se trabaja con el valor absoluto en `u128`, donde
+// caben todos, y el resultado se convierte de vuelta al tipo original. Con signo:
+// • `gcd`, `lcm`, `divisors`, `factorize` y `totient` usan el valor absoluto;
+// • no hay primos negativos, y `primes` empieza como muy pronto en `2`.
+// Las funciones que devuelven `T` entran en pánico si el resultado no cabe en `T`
+// (p.ej. `gcd(i8::MIN, 0)` sería `128`); `checked_lcm` devuelve `None` en su lugar.
+
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+use crate::Divisible;
+
+// Lo que necesitamos de cada tipo entero, además de `Divisible`.
+pub trait Integer: Divisible + Ord + fmt::Debug {
+    const MIN: Self;
+    const MAX: Self;
+
+    // El valor absoluto, que siempre cabe en un `u128`.
+    fn unsigned_abs(self) -> u128;
+
+    fn is_negative(self) -> bool;
+
+    // `None` si `n` no cabe en el tipo.
+    fn from_u128(n: u128) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const MIN: $t = <$t>::MIN;
+                const MAX: $t = <$t>::MAX;
+
+                fn unsigned_abs(self) -> u128 {
+                    self as u128
+                }
+
+                fn is_negative(self) -> bool {
+                    false
+                }
+
+                fn from_u128(n: u128) -> Option<$t> {
+                    <$t>::try_from(n).ok()
+                }
+            }
+        )*
+    };
+    (signed: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const MIN: $t = <$t>::MIN;
+                const MAX: $t = <$t>::MAX;
+
+                fn unsigned_abs(self) -> u128 {
+                    <$t>::unsigned_abs(self) as u128
+                }
+
+                fn is_negative(self) -> bool {
+                    self < 0
+                }
+
+                fn from_u128(n: u128) -> Option<$t> {
+                    <$t>::try_from(n).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+
+fn to_type<T: Integer>(n: u128) -> T {
+    T::from_u128(n).unwrap_or_else(|| panic!("el resultado {} no cabe en el tipo", n))
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Máximo común divisor (siempre no negativo; `gcd(0, 0)` es `0`).
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    to_type(gcd_u128(a.unsigned_abs(), b.unsigned_abs()))
+}
+
+// Mínimo común múltiplo (no negativo), o `None` si no cabe en `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    if a == 0 || b == 0 {
+        return T::from_u128(0);
+    }
+    (a / gcd_u128(a, b)).checked_mul(b).and_then(T::from_u128)
+}
+
+// Mínimo común múltiplo; entra en pánico si no cabe en `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).unwrap_or_else(|| panic!("el mínimo común múltiplo no cabe en el tipo"))
+}
+
+// Resultado del algoritmo de Euclides extendido: `a * x + b * y == gcd`.
+// Los coeficientes son `i128` porque pueden ser negativos aunque `T` no tenga signo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendedGcd<T> {
+    pub gcd: T,
+    pub x: i128,
+    pub y: i128,
+}
+
+// Euclides sobre los valores absolutos. Los coeficientes finales cumplen
+// `|x| <= b / gcd` y `|y| <= a / gcd`, así que caben en un `i128`; los intermedios
+// pueden desbordarse, pero con aritmética modular (`wrapping_*`) el resultado es exacto.
+fn extended_gcd_u128(a: u128, b: u128) -> (u128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    let (mut old_t, mut t) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s.wrapping_sub((q as i128).wrapping_mul(s)));
+        (old_t, t) = (t, old_t.wrapping_sub((q as i128).wrapping_mul(t)));
+    }
+    (old_r, old_s, old_t)
+}
+
+// Euclides extendido: el máximo común divisor y unos coeficientes de Bézout.
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> ExtendedGcd<T> {
+    let (g, x, y) = extended_gcd_u128(a.unsigned_abs(), b.unsigned_abs());
+
+    // `|a| * x == a * (-x)` si `a` es negativo.
+    ExtendedGcd {
+        gcd: to_type(g),
+        x: if a.is_negative() { x.wrapping_neg() } else { x },
+        y: if b.is_negative() { y.wrapping_neg() } else { y },
+    }
+}
+
+// El inverso de `a` módulo `m` (en `0..m`), o `None` si no existe: `a` y `m` no son
+// coprimos o `m` no es positivo.
+pub fn mod_inverse<T: Integer>(a: T, m: T) -> Option<T> {
+    if m.is_negative() || m.unsigned_abs() == 0 {
+        return None;
+    }
+
+    let m = m.unsigned_abs();
+    let a = match a.unsigned_abs() % m {
+        rem if a.is_negative() && rem != 0 => m - rem,
+        rem => rem,
+    };
+
+    let (g, x, _) = extended_gcd_u128(a, m);
+    if g != 1 {
+        return None;
+    }
+
+    let x = match x.unsigned_abs() % m {
+        rem if x < 0 && rem != 0 => m - rem,
+        rem => rem,
+    };
+    T::from_u128(x % m)
+}
+
+// Aritmética modular en `u128`, sin desbordamientos.
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if m <= u128::from(u64::MAX) {
+        // `a, b < m < 2^64`: el producto cabe.
+        return a % m * (b % m) % m;
+    }
+
+    // Multiplicación "a la rusa": sumas y duplicaciones módulo `m`.
+    let (mut a, mut b, mut result) = (a % m, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn pow_mod(mut base: u128, mut exp: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+// Con estas bases, Miller–Rabin no se equivoca para ningún `n < 3.3·10^24`
+// (en particular, para ningún `u64`); por encima es un test probabilístico.
+const WITNESSES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+fn is_prime_u128(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_divisible_by(p) {
+            return n == p;
+        }
+    }
+
+    // `n - 1 = d * 2^s` con `d` impar.
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+// ¿Es `n` primo? Test de Miller–Rabin (determinista para todo `u64`).
+pub fn is_prime<T: Integer>(n: T) -> bool {
+    !n.is_negative() && is_prime_u128(n.unsigned_abs())
+}
+
+// Un divisor propio de `n` (compuesto e impar) con el método rho de Pollard:
+// la sucesión `x → x² + c (mod n)` acaba repitiéndose módulo cualquier factor `p`
+// mucho antes que módulo `n`, y `gcd(|x - y|, n)` lo delata.
+fn pollard_rho(n: u128) -> u128 {
+    for c in 1.. {
+        let f = |x| add_mod(mul_mod(x, x, n), c, n);
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd_u128(x.abs_diff(y), n);
+        }
+        // Con `d == n` la sucesión ha fallado: probamos con otra constante.
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+// Añade a `factors` los factores primos de `n` (sin factores pequeños), con repetición.
+fn split(n: u128, factors: &mut Vec<u128>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime_u128(n) {
+        factors.push(n);
+        return;
+    }
+    let d = pollard_rho(n);
+    split(d, factors);
+    split(n / d, factors);
+}
+
+// Los factores primos de `n` con su exponente, de menor a mayor:
+// `factorize(360u32) == [(2, 3), (3, 2), (5, 1)]`. El `0` y el `1` no tienen.
+pub fn factorize<T: Integer>(n: T) -> Vec<(T, u32)> {
+    let mut n = n.unsigned_abs();
+    let mut factors = Vec::new();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    // Los factores pequeños, por división por tentativa.
+    let mut d = 2;
+    while d < 1000 && d * d <= n {
+        while n.is_divisible_by(d) {
+            factors.push(d);
+            n /= d;
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    // El resto es `1`, un primo o un producto de factores grandes.
+    split(n, &mut factors);
+    factors.sort_unstable();
+
+    let mut grouped: Vec<(T, u32)> = Vec::new();
+    for p in factors {
+        match grouped.last_mut() {
+            Some((last, exp)) if last.unsigned_abs() == p => *exp += 1,
+            _ => grouped.push((to_type(p), 1)),
+        }
+    }
+    grouped
+}
+
+// La función φ de Euler: cuántos números de `1..=n` son coprimos con `n`.
+pub fn totient<T: Integer>(n: T) -> T {
+    let phi = factorize(n)
+        .into_iter()
+        .fold(n.unsigned_abs(), |phi, (p, _)| {
+            phi / p.unsigned_abs() * (p.unsigned_abs() - 1)
+        });
+    to_type(phi)
+}
+
+// Los divisores positivos de `n`, de menor a mayor. El `0` no tiene (serían todos).
+#[derive(Debug, Clone)]
+pub struct Divisors<T> {
+    inner: std::vec::IntoIter<T>,
+}
+
+impl<T> Iterator for Divisors<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Divisors<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Divisors<T> {}
+
+// Los divisores se generan a partir de la factorización, así que no hace falta
+// probar todos los números hasta `√n`.
+pub fn divisors<T: Integer>(n: T) -> Divisors<T> {
+    let mut all = if n.unsigned_abs() == 0 {
+        Vec::new()
+    } else {
+        vec![1u128]
+    };
+
+    for (p, exp) in factorize(n) {
+        let p = p.unsigned_abs();
+        let previous = all.len();
+        let mut power = 1;
+        for _ in 0..exp {
+            power *= p;
+            for i in 0..previous {
+                all.push(all[i] * power);
+            }
+        }
+    }
+    all.sort_unstable();
+
+    Divisors {
+        inner: all.into_iter().map(to_type).collect::<Vec<T>>().into_iter(),
+    }
+}
+
+// Números que se criban de una vez.
+const SEGMENT_LEN: u128 = 1 << 16;
+
+// Cuánto pueden crecer los primos base para cribar un solo trozo.
+const BASE_GROWTH_LIMIT: u128 = 16 * SEGMENT_LEN;
+
+// Marca los compuestos de `low..low + len` con los primos de `base`, que deben
+// incluir todos los primos hasta `√(low + len - 1)`.
+fn sieve_segment(low: u128, len: u128, base: &[u128]) -> Vec<bool> {
+    let mut composite = vec![false; len as usize];
+    let high = low + len - 1;
+
+    for &p in base.iter().take_while(|&&p| p * p <= high) {
+        // Cerca de `u128::MAX` el siguiente múltiplo puede no existir.
+        let mut multiple = low.div_ceil(p).checked_mul(p).map(|first| first.max(p * p));
+        while let Some(n) = multiple.filter(|&n| n <= high) {
+            composite[(n - low) as usize] = true;
+            multiple = n.checked_add(p);
+        }
+    }
+    // El `0` y el `1` no son primos.
+    for n in low..2.min(high + 1) {
+        composite[(n - low) as usize] = true;
+    }
+    composite
+}
+
+// Criba de Eratóstenes segmentada sobre un rango: recorre el rango en trozos de
+// `SEGMENT_LEN` números, así que la memoria no depende de su longitud. Los primos
+// "base" (hasta `√n`) se van calculando, también por trozos, según hacen falta.
+// Si un trozo necesitara demasiados primos base nuevos (un rango corto muy lejos
+// del `0`, como `u64::MAX - 1000..`), sus números se comprueban con Miller–Rabin.
+#[derive(Debug, Clone)]
+pub struct Primes<T> {
+    // Primos hasta `base_limit`, incluido.
+    base: Vec<u128>,
+    base_limit: u128,
+    // Siguiente número por cribar y último del rango.
+    next: u128,
+    end: u128,
+    // Primos del trozo actual que aún no se han devuelto (en orden inverso).
+    pending: Vec<T>,
+}
+
+impl<T: Integer> Primes<T> {
+    // Amplía `base` hasta incluir todos los primos `<= limit`.
+    fn extend_base(&mut self, limit: u128) {
+        while self.base_limit < limit {
+            // Con los primos hasta `base_limit` se puede cribar hasta `base_limit²`.
+            let high = limit
+                .min(self.base_limit.saturating_mul(self.base_limit))
+                .min(self.base_limit + SEGMENT_LEN);
+            let low = self.base_limit + 1;
+            let composite = sieve_segment(low, high - low + 1, &self.base);
+            self.base.extend(
+                (low..=high)
+                    .zip(composite)
+                    .filter(|&(_, composite)| !composite)
+                    .map(|(n, _)| n),
+            );
+            self.base_limit = high;
+        }
+    }
+}
+
+impl<T: Integer> Iterator for Primes<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.pending.is_empty() {
+            if self.next > self.end {
+                return None;
+            }
+
+            let low = self.next;
+            let high = self.end.min(low.saturating_add(SEGMENT_LEN - 1));
+            let needed = high.isqrt();
+
+            self.pending = if needed > self.base_limit + BASE_GROWTH_LIMIT {
+                (low..=high)
+                    .filter(|&n| is_prime_u128(n))
+                    .map(to_type)
+                    .collect()
+            } else {
+                self.extend_base(needed);
+                let composite = sieve_segment(low, high - low + 1, &self.base);
+                (low..=high)
+                    .zip(composite)
+                    .filter(|&(_, composite)| !composite)
+                    .map(|(n, _)| to_type(n))
+                    .collect()
+            };
+            self.pending.reverse();
+
+            match high.checked_add(1) {
+                Some(next) => self.next = next,
+                // `high == u128::MAX`: no queda nada más.
+                None => self.end = 0,
+            }
+        }
+        self.pending.pop()
+    }
+}
+
+// Los primos de `range`, de menor a mayor, p.ej. `primes(1_000_000u64..1_000_100)`.
+// Sin extremo inferior se empieza en `2`; sin extremo superior, en `T::MAX`.
+pub fn primes<T: Integer, R: RangeBounds<T>>(range: R) -> Primes<T> {
+    // Los negativos no importan: se empieza como muy pronto en `2`.
+    let start = match range.start_bound() {
+        Bound::Included(&start) if !start.is_negative() => Some(start.unsigned_abs()),
+        Bound::Excluded(&start) if !start.is_negative() => start.unsigned_abs().checked_add(1),
+        _ => Some(0),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) if !end.is_negative() => Some(end.unsigned_abs()),
+        Bound::Excluded(&end) if !end.is_negative() => end.unsigned_abs().checked_sub(1),
+        Bound::Unbounded => Some(T::MAX.unsigned_abs()),
+        _ => None,
+    };
+    // Un rango vacío: `next > end`.
+    let (next, end) = match (start, end) {
+        (Some(start), Some(end)) => (start.max(2), end),
+        _ => (1, 0),
+    };
+
+    // Para empezar a cribar hacen falta algunos primos base.
+    Primes {
+        base: vec![2, 3],
+        base_limit: 3,
+        next,
+        end,
+        pending: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_prime_naive(n: u64) -> bool {
+        n >= 2
+            && (2..n)
+                .take_while(|d| d * d <= n)
+                .all(|d| !n.is_multiple_of(d))
+    }
+
+    #[test]
+    fn is_prime_matches_trial_division() {
+        for n in 0..10_000u64 {
+            assert_eq!(is_prime(n), is_prime_naive(n), "n = {n}");
+        }
+        assert!(!is_prime(-7i32));
+    }
+
+    #[test]
+    fn strong_pseudoprimes_are_composite() {
+        // Engañan a Miller–Rabin con las primeras bases, pero no con todas.
+        assert!(!is_prime(3_215_031_751u64));
+        assert!(!is_prime(3_825_123_056_546_413_051u64));
+        assert!(is_prime(u64::MAX - 58));
+    }
+
+    #[test]
+    fn factorizes_large_semiprimes() {
+        let (p, q) = (1_073_741_789u64, 1_073_741_783u64);
+        assert!(is_prime(p) && is_prime(q));
+        assert_eq!(factorize(p * q), [(q, 1), (p, 1)]);
+        assert_eq!(factorize(p * p), [(p, 2)]);
+
+        assert_eq!(
+            factorize(u64::MAX),
+            [
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        assert!(factorize(0u64).is_empty());
+        assert!(factorize(1u64).is_empty());
+    }
+
+    #[test]
+    fn primes_match_is_prime() {
+        let expected: Vec<u64> = (1_000_000..1_000_100).filter(|&n| is_prime(n)).collect();
+        assert_eq!(
+            primes(1_000_000u64..1_000_100).collect::<Vec<_>>(),
+            expected
+        );
+
+        let start = u64::MAX - 1_000;
+        let expected: Vec<u64> = (start..=u64::MAX).filter(|&n| is_prime(n)).collect();
+        assert_eq!(primes(start..).collect::<Vec<_>>(), expected);
+        assert_eq!(expected.last(), Some(&(u64::MAX - 58)));
+
+        assert_eq!(primes(-10i32..12).collect::<Vec<_>>(), [2, 3, 5, 7, 11]);
+    }
+
+    #[test]
+    fn signed_inputs() {
+        assert_eq!(factorize(-360i32), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(3, -11), None);
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(checked_lcm(i8::MIN, 3), None);
+
+        let ExtendedGcd { gcd, x, y } = extended_gcd(-240i64, 46);
+        assert_eq!(gcd, 2);
+        assert_eq!(-240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn totient_and_divisors() {
+        assert_eq!(totient(36u32), 12);
+        assert_eq!(totient(1u32), 1);
+        assert_eq!(
+            divisors(36u32).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 6, 9, 12, 18, 36]
+        );
+        assert_eq!(divisors(0u32).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "no cabe en el tipo")]
+    fn gcd_panics_when_result_does_not_fit() {
+        gcd(i8::MIN, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "no cabe en el tipo")]
+    fn lcm_panics_when_result_does_not_fit() {
+        lcm(200u8, 3);
+    }
+}
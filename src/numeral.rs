@@ -0,0 +1,409 @@
+// Otras formas de escribir los números.
+// =============================================================
+// Cuando ninguna regla coincide, FizzBuzz escribe el propio número. Con
+// `NumberFormat` se puede escribir en decimal (lo normal), binario, octal,
+// hexadecimal, con números romanos o en palabras en español o en inglés:
+// 101 → "ciento uno" / "one hundred one".
+//
+// • Los números romanos no tienen cero ni (sin rayas encima) pasan de 3999:
+//   fuera de `1..=3999` se escriben en decimal.
+// • En español se usa la escala larga (millón = 10^6, billón = 10^12,
+//   trillón = 10^18) y en inglés la corta (billion = 10^9, trillion = 10^12, ...),
+//   así que se cubre todo `u64` en los dos idiomas.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NumberFormat {
+    #[default]
+    Decimal,
+    Binary,
+    Octal,
+    // En minúsculas, como `{:x}`.
+    Hex,
+    Roman,
+    Spanish,
+    English,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownNumberFormat(pub String);
+
+impl fmt::Display for UnknownNumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.unknown_number_format", self.0))
+    }
+}
+
+impl Error for UnknownNumberFormat {}
+
+impl FromStr for NumberFormat {
+    type Err = UnknownNumberFormat;
+
+    fn from_str(s: &str) -> Result<NumberFormat, UnknownNumberFormat> {
+        match s {
+            "decimal" => Ok(NumberFormat::Decimal),
+            "binary" => Ok(NumberFormat::Binary),
+            "octal" => Ok(NumberFormat::Octal),
+            "hex" => Ok(NumberFormat::Hex),
+            "roman" => Ok(NumberFormat::Roman),
+            "spanish" => Ok(NumberFormat::Spanish),
+            "english" => Ok(NumberFormat::English),
+            other => Err(UnknownNumberFormat(other.to_owned())),
+        }
+    }
+}
+
+impl NumberFormat {
+    // Un valor que se escribe con `{}` en este formato, sin crear un `String`
+    // (salvo para las palabras).
+    pub fn display(self, n: u64) -> FormattedNumber {
+        FormattedNumber { n, format: self }
+    }
+
+    pub fn format(self, n: u64) -> String {
+        self.display(n).to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormattedNumber {
+    n: u64,
+    format: NumberFormat,
+}
+
+impl fmt::Display for FormattedNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.n;
+        match self.format {
+            NumberFormat::Decimal => write!(f, "{}", n),
+            NumberFormat::Binary => write!(f, "{:b}", n),
+            NumberFormat::Octal => write!(f, "{:o}", n),
+            NumberFormat::Hex => write!(f, "{:x}", n),
+            NumberFormat::Roman if (1..=3999).contains(&n) => f.write_str(&roman(n)),
+            NumberFormat::Roman => write!(f, "{}", n),
+            NumberFormat::Spanish => f.write_str(&spanish(n)),
+            NumberFormat::English => f.write_str(&english(n)),
+        }
+    }
+}
+
+// Números romanos, de mayor a menor valor (con las restas `CM`, `XC`, ...).
+fn roman(mut n: u64) -> String {
+    const SYMBOLS: [(u64, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut roman = String::new();
+    for (value, symbol) in SYMBOLS {
+        while n >= value {
+            roman.push_str(symbol);
+            n -= value;
+        }
+    }
+    roman
+}
+
+// Español.
+
+const SPANISH_UNITS: [&str; 30] = [
+    "cero",
+    "uno",
+    "dos",
+    "tres",
+    "cuatro",
+    "cinco",
+    "seis",
+    "siete",
+    "ocho",
+    "nueve",
+    "diez",
+    "once",
+    "doce",
+    "trece",
+    "catorce",
+    "quince",
+    "dieciséis",
+    "diecisiete",
+    "dieciocho",
+    "diecinueve",
+    "veinte",
+    "veintiuno",
+    "veintidós",
+    "veintitrés",
+    "veinticuatro",
+    "veinticinco",
+    "veintiséis",
+    "veintisiete",
+    "veintiocho",
+    "veintinueve",
+];
+
+const SPANISH_TENS: [&str; 10] = [
+    "",
+    "",
+    "",
+    "treinta",
+    "cuarenta",
+    "cincuenta",
+    "sesenta",
+    "setenta",
+    "ochenta",
+    "noventa",
+];
+
+const SPANISH_HUNDREDS: [&str; 10] = [
+    "",
+    "ciento",
+    "doscientos",
+    "trescientos",
+    "cuatrocientos",
+    "quinientos",
+    "seiscientos",
+    "setecientos",
+    "ochocientos",
+    "novecientos",
+];
+
+// `1..=999`. Delante de "mil", "millón", etc., "uno" se apocopa: "veintiún mil",
+// "treinta y un millones" (`apocope`).
+fn spanish_below_thousand(n: u64, apocope: bool, words: &mut Vec<&'static str>) {
+    let (hundreds, rest) = (n / 100, n % 100);
+    match (hundreds, rest) {
+        (0, _) => {}
+        (1, 0) => words.push("cien"),
+        _ => words.push(SPANISH_HUNDREDS[hundreds as usize]),
+    }
+
+    let (tens, units) = (rest / 10, rest % 10);
+    match rest {
+        0 => {}
+        1 if apocope => words.push("un"),
+        21 if apocope => words.push("veintiún"),
+        1..=29 => words.push(SPANISH_UNITS[rest as usize]),
+        _ => {
+            words.push(SPANISH_TENS[tens as usize]);
+            match units {
+                0 => {}
+                1 if apocope => words.extend(["y", "un"]),
+                _ => words.extend(["y", SPANISH_UNITS[units as usize]]),
+            }
+        }
+    }
+}
+
+// `1..=999_999`: "mil" no lleva "un" delante.
+fn spanish_below_million(n: u64, apocope: bool, words: &mut Vec<&'static str>) {
+    let (thousands, rest) = (n / 1000, n % 1000);
+    match thousands {
+        0 => {}
+        1 => words.push("mil"),
+        _ => {
+            spanish_below_thousand(thousands, true, words);
+            words.push("mil");
+        }
+    }
+    spanish_below_thousand(rest, apocope, words);
+}
+
+fn spanish(n: u64) -> String {
+    if n == 0 {
+        return SPANISH_UNITS[0].to_owned();
+    }
+
+    // Grupos de seis cifras: trillones, billones, millones y el resto.
+    const SCALES: [(u64, &str, &str); 3] = [
+        (1_000_000_000_000_000_000, "trillón", "trillones"),
+        (1_000_000_000_000, "billón", "billones"),
+        (1_000_000, "millón", "millones"),
+    ];
+
+    let mut words = Vec::new();
+    let mut rest = n;
+    for (scale, singular, plural) in SCALES {
+        let count = rest / scale;
+        rest %= scale;
+        if count > 0 {
+            spanish_below_million(count, true, &mut words);
+            words.push(if count == 1 { singular } else { plural });
+        }
+    }
+    spanish_below_million(rest, false, &mut words);
+    words.join(" ")
+}
+
+// Inglés.
+
+const ENGLISH_UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const ENGLISH_TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// `1..=999`, con guion en las decenas ("forty-two") y sin "and".
+fn english_below_thousand(n: u64, words: &mut Vec<String>) {
+    let (hundreds, rest) = (n / 100, n % 100);
+    if hundreds > 0 {
+        words.push(format!("{} hundred", ENGLISH_UNITS[hundreds as usize]));
+    }
+
+    let (tens, units) = (rest / 10, rest % 10);
+    match rest {
+        0 => {}
+        1..=19 => words.push(ENGLISH_UNITS[rest as usize].to_owned()),
+        _ if units == 0 => words.push(ENGLISH_TENS[tens as usize].to_owned()),
+        _ => words.push(format!(
+            "{}-{}",
+            ENGLISH_TENS[tens as usize], ENGLISH_UNITS[units as usize]
+        )),
+    }
+}
+
+fn english(n: u64) -> String {
+    if n == 0 {
+        return ENGLISH_UNITS[0].to_owned();
+    }
+
+    // Grupos de tres cifras, de mayor a menor.
+    const SCALES: [(u64, &str); 6] = [
+        (1_000_000_000_000_000_000, "quintillion"),
+        (1_000_000_000_000_000, "quadrillion"),
+        (1_000_000_000_000, "trillion"),
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+    ];
+
+    let mut words = Vec::new();
+    let mut rest = n;
+    for (scale, name) in SCALES {
+        let count = rest / scale;
+        rest %= scale;
+        if count > 0 {
+            english_below_thousand(count, &mut words);
+            words.push(name.to_owned());
+        }
+    }
+    english_below_thousand(rest, &mut words);
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spanish_words() {
+        let cases = [
+            (0, "cero"),
+            (1, "uno"),
+            (21, "veintiuno"),
+            (100, "cien"),
+            (101, "ciento uno"),
+            (1000, "mil"),
+            (21_000, "veintiún mil"),
+            (31_001, "treinta y un mil uno"),
+            (1_000_000, "un millón"),
+            (21_000_000, "veintiún millones"),
+            (1_000_000_000, "mil millones"),
+            (1_000_000_000_000, "un billón"),
+            (
+                u64::MAX,
+                "dieciocho trillones cuatrocientos cuarenta y seis mil setecientos cuarenta y \
+                 cuatro billones setenta y tres mil setecientos nueve millones quinientos \
+                 cincuenta y un mil seiscientos quince",
+            ),
+        ];
+        for (n, words) in cases {
+            assert_eq!(NumberFormat::Spanish.format(n), words, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn english_words() {
+        let cases = [
+            (0, "zero"),
+            (1, "one"),
+            (21, "twenty-one"),
+            (100, "one hundred"),
+            (101, "one hundred one"),
+            (1000, "one thousand"),
+            (21_000, "twenty-one thousand"),
+            (1_000_000, "one million"),
+            (21_000_000, "twenty-one million"),
+            (1_000_000_000, "one billion"),
+            (
+                u64::MAX,
+                "eighteen quintillion four hundred forty-six quadrillion seven hundred \
+                 forty-four trillion seventy-three billion seven hundred nine million five \
+                 hundred fifty-one thousand six hundred fifteen",
+            ),
+        ];
+        for (n, words) in cases {
+            assert_eq!(NumberFormat::English.format(n), words, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn roman_numerals() {
+        let cases = [
+            (1, "I"),
+            (4, "IV"),
+            (1994, "MCMXCIV"),
+            (3999, "MMMCMXCIX"),
+            // Fuera de `1..=3999`, en decimal.
+            (0, "0"),
+            (4000, "4000"),
+        ];
+        for (n, roman) in cases {
+            assert_eq!(NumberFormat::Roman.format(n), roman, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn other_bases_and_parsing() {
+        assert_eq!(NumberFormat::Binary.format(10), "1010");
+        assert_eq!(NumberFormat::Octal.format(64), "100");
+        assert_eq!(NumberFormat::Hex.format(255), "ff");
+        assert_eq!("roman".parse(), Ok(NumberFormat::Roman));
+        assert_eq!(
+            "klingon".parse::<NumberFormat>(),
+            Err(UnknownNumberFormat("klingon".to_owned()))
+        );
+    }
+}
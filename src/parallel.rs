@@ -0,0 +1,219 @@
+// Generación de FizzBuzz en paralelo, manteniendo el orden.
+// =============================================================
+// Para rangos de miles de millones de números, el rango se divide en trozos que
+// generan varios hilos (`std::thread`) con el generador rápido. El hilo que llama
+// recoge los trozos terminados y los escribe en su orden original, así que la
+// salida es idéntica a la secuencial.
+//
+// Para que la memoria no crezca sin límite, un hilo no puede empezar un trozo si
+// está a `max_in_flight` trozos o más del siguiente que falta por escribir.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::sync::mpsc;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+use crate::write_fizzbuzz_range_fast;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelConfig {
+    // Número de hilos generadores.
+    pub threads: usize,
+    // Números por trozo.
+    pub chunk_len: u64,
+    // Trozos generados (o en generación) que aún no se han escrito, como mucho.
+    pub max_in_flight: usize,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> ParallelConfig {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+        ParallelConfig {
+            threads,
+            chunk_len: 1 << 20,
+            max_in_flight: 2 * threads,
+        }
+    }
+}
+
+// Estado compartido entre los hilos generadores y el escritor.
+struct Schedule {
+    // Siguiente trozo que se puede repartir.
+    next_chunk: u128,
+    // Siguiente trozo que se tiene que escribir.
+    next_to_write: u128,
+    // El escritor ha fallado: los hilos deben terminar.
+    aborted: bool,
+}
+
+// Escribe las líneas de `range` generándolas en paralelo según `config`.
+pub fn write_fizzbuzz_range_parallel<W: Write>(
+    out: &mut W,
+    range: RangeInclusive<u64>,
+    config: &ParallelConfig,
+) -> io::Result<()> {
+    let (start, end) = range.into_inner();
+    if start > end {
+        return Ok(());
+    }
+
+    // Los valores `0` no tienen sentido: se tratan como `1`.
+    let threads = config.threads.max(1);
+    let chunk_len = u128::from(config.chunk_len.max(1));
+    let max_in_flight = config.max_in_flight.max(1) as u128;
+
+    let len = u128::from(end - start) + 1;
+    let chunks = len.div_ceil(chunk_len);
+
+    let schedule = Mutex::new(Schedule {
+        next_chunk: 0,
+        next_to_write: 0,
+        aborted: false,
+    });
+    let written = Condvar::new();
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<(u128, Vec<u8>)>();
+
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (schedule, written) = (&schedule, &written);
+
+            scope.spawn(move || loop {
+                // Esperamos a que haya un trozo disponible dentro de la ventana.
+                let chunk = {
+                    let mut state = schedule.lock().unwrap();
+                    while !state.aborted
+                        && state.next_chunk < chunks
+                        && state.next_chunk >= state.next_to_write + max_in_flight
+                    {
+                        state = written.wait(state).unwrap();
+                    }
+                    if state.aborted || state.next_chunk >= chunks {
+                        return;
+                    }
+                    state.next_chunk += 1;
+                    state.next_chunk - 1
+                };
+
+                // No se desborda: el trozo está dentro de `start..=end`.
+                let first = u128::from(start) + chunk * chunk_len;
+                let last = (first + chunk_len - 1).min(u128::from(end));
+
+                let mut buffer = Vec::new();
+                write_fizzbuzz_range_fast(&mut buffer, first as u64..=last as u64)
+                    .expect("escribir en un `Vec` no falla");
+                if sender.send((chunk, buffer)).is_err() {
+                    return;
+                }
+            });
+        }
+        // Solo quedan los emisores de los hilos: cuando terminen todos, `recv` fallará.
+        drop(sender);
+
+        // El hilo que llama escribe los trozos en orden.
+        let mut pending = BTreeMap::new();
+        let mut next_to_write = 0;
+        let result = receiver
+            .iter()
+            .try_for_each(|(chunk, buffer)| -> io::Result<()> {
+                pending.insert(chunk, buffer);
+
+                while let Some(buffer) = pending.remove(&next_to_write) {
+                    out.write_all(&buffer)?;
+                    next_to_write += 1;
+
+                    schedule.lock().unwrap().next_to_write = next_to_write;
+                    written.notify_all();
+                }
+                Ok(())
+            });
+
+        if result.is_err() {
+            schedule.lock().unwrap().aborted = true;
+            written.notify_all();
+        }
+        result
+    })?;
+
+    out.flush()
+}
+
+// Equivalente paralelo de `fizzbuzz_to(n)` sobre cualquier `Write`.
+pub fn write_fizzbuzz_parallel<W: Write>(
+    out: &mut W,
+    n: u64,
+    config: &ParallelConfig,
+) -> io::Result<()> {
+    write_fizzbuzz_range_parallel(out, 1..=n, config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequential(range: RangeInclusive<u64>) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_fizzbuzz_range_fast(&mut out, range).unwrap();
+        out
+    }
+
+    fn parallel(range: RangeInclusive<u64>, config: &ParallelConfig) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_fizzbuzz_range_parallel(&mut out, range, config).unwrap();
+        out
+    }
+
+    #[test]
+    fn matches_sequential_output() {
+        // Trozos pequeños y pocos en vuelo para que los hilos se adelanten y esperen.
+        for config in [
+            ParallelConfig {
+                threads: 4,
+                chunk_len: 97,
+                max_in_flight: 2,
+            },
+            ParallelConfig {
+                threads: 3,
+                chunk_len: 1_000,
+                max_in_flight: 8,
+            },
+            ParallelConfig {
+                threads: 0,
+                chunk_len: 0,
+                max_in_flight: 0,
+            },
+        ] {
+            for range in [1..=1, 1..=10_000, 9_990..=10_010, 123_456..=140_000] {
+                assert_eq!(
+                    parallel(range.clone(), &config),
+                    sequential(range.clone()),
+                    "{range:?} con {config:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn top_of_u64_range() {
+        let config = ParallelConfig {
+            threads: 4,
+            chunk_len: 300,
+            max_in_flight: 4,
+        };
+        let range = u64::MAX - 2_000..=u64::MAX;
+        assert_eq!(parallel(range.clone(), &config), sequential(range));
+    }
+
+    #[test]
+    fn write_parallel_matches_fizzbuzz_to() {
+        let mut expected = Vec::new();
+        crate::write_fizzbuzz_to(&mut expected, 5_000).unwrap();
+        let mut out = Vec::new();
+        write_fizzbuzz_parallel(&mut out, 5_000, &ParallelConfig::default()).unwrap();
+        assert_eq!(out, expected);
+    }
+}
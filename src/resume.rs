@@ -0,0 +1,622 @@
+// Volcados reanudables.
+// =============================================================
+// Generar billones de líneas lleva horas: si el proceso se interrumpe, no queremos
+// empezar de cero. `write_fizzbuzz_resumable` escribe en un fichero y, cada cierto
+// número de líneas, vacía el búfer, sincroniza el disco y guarda un punto de control
+// en un fichero auxiliar (`<fichero>.checkpoint`) con:
+// • la última línea escrita (`n`),
+// • el byte en el que termina,
+// • un resumen (`hash`) de las reglas, para no mezclar salidas de reglas distintas.
+//
+// Al reanudar se comprueba que las últimas líneas antes de ese byte son las que
+// tocan, se descarta lo que se escribió después del punto de control (puede estar a
+// medias) y se sigue añadiendo a partir de la línea siguiente.
+//
+// El punto de control se escribe en un fichero temporal que luego se renombra, así
+// que nunca queda a medio escribir.
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::{write_fizzbuzz_range_fast, Fallback, FizzBuzzRules, NumberFormat, RuleMode};
+
+// Líneas que se comprueban al final del fichero antes de reanudar.
+const TAIL_LINES: u64 = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    // Última línea escrita por completo (`0` si todavía ninguna).
+    pub last_n: u64,
+    // Byte en el que termina esa línea.
+    pub byte_offset: u64,
+    // `FizzBuzzRules::rules_hash` de las reglas con las que se escribió.
+    pub rules_hash: u64,
+}
+
+// Primera línea del fichero auxiliar, para reconocerlo (y cambiar el formato algún día).
+const CHECKPOINT_HEADER: &str = "fizzbuzz-checkpoint 1";
+
+impl Checkpoint {
+    // El fichero auxiliar de `output`: `salida.txt` → `salida.txt.checkpoint`.
+    pub fn path_for(output: &Path) -> PathBuf {
+        let mut path = output.as_os_str().to_owned();
+        path.push(".checkpoint");
+        PathBuf::from(path)
+    }
+
+    // Lee el punto de control de `path`, o `None` si no existe.
+    pub fn read(path: &Path) -> Result<Option<Checkpoint>, ResumeError> {
+        match fs::read_to_string(path) {
+            Ok(text) => text.parse().map(Some),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    // Lo guarda en `path` sin dejarlo nunca a medias: primero en un temporal
+    // sincronizado con el disco y después se renombra.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(self.to_string().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    }
+}
+
+impl fmt::Display for Checkpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", CHECKPOINT_HEADER)?;
+        writeln!(f, "n={}", self.last_n)?;
+        writeln!(f, "offset={}", self.byte_offset)?;
+        writeln!(f, "rules={:016x}", self.rules_hash)
+    }
+}
+
+impl FromStr for Checkpoint {
+    type Err = ResumeError;
+
+    fn from_str(s: &str) -> Result<Checkpoint, ResumeError> {
+        let corrupt = ResumeError::Corrupt;
+
+        let mut lines = s.lines();
+        if lines.next() != Some(CHECKPOINT_HEADER) {
+            return Err(corrupt(crate::message!("resume.corrupt.header")));
+        }
+        let mut field = |name: &str, radix: u32| {
+            lines
+                .next()
+                .and_then(|line| line.strip_prefix(name)?.strip_prefix('='))
+                .and_then(|value| u64::from_str_radix(value, radix).ok())
+                .ok_or_else(|| corrupt(crate::message!("resume.corrupt.field", name)))
+        };
+
+        Ok(Checkpoint {
+            last_n: field("n", 10)?,
+            byte_offset: field("offset", 10)?,
+            rules_hash: field("rules", 16)?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum ResumeError {
+    Io(io::Error),
+    // Las reglas con cierres no se pueden resumir en un `hash`.
+    NotResumable,
+    // El fichero de salida ya existe, pero no hay punto de control.
+    NoCheckpoint(PathBuf),
+    // El punto de control se hizo con otras reglas.
+    RulesChanged { expected: u64, found: u64 },
+    // El punto de control no se puede leer o no encaja con el fichero.
+    Corrupt(String),
+    // Las líneas de antes del punto de control no son las que tocan.
+    TailMismatch { line: u64 },
+    // El fichero ya llega más allá de la línea pedida.
+    BeyondEnd { last_n: u64, n: u64 },
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ResumeError::Io(err) => write!(f, "{}", err),
+            ResumeError::NotResumable => {
+                f.write_str(&crate::message!("resume.error.not_resumable"))
+            }
+            ResumeError::NoCheckpoint(path) => f.write_str(&crate::message!(
+                "resume.error.no_checkpoint",
+                path.display()
+            )),
+            ResumeError::RulesChanged { expected, found } => f.write_str(&crate::message!(
+                "resume.error.rules_changed",
+                format!("{:016x}", found),
+                format!("{:016x}", expected)
+            )),
+            ResumeError::Corrupt(message) => {
+                f.write_str(&crate::message!("resume.error.corrupt", message))
+            }
+            ResumeError::TailMismatch { line } => {
+                f.write_str(&crate::message!("resume.error.tail_mismatch", line))
+            }
+            ResumeError::BeyondEnd { last_n, n } => {
+                f.write_str(&crate::message!("resume.error.beyond_end", last_n, n))
+            }
+        }
+    }
+}
+
+impl Error for ResumeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ResumeError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ResumeError {
+    fn from(err: io::Error) -> ResumeError {
+        ResumeError::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeOptions {
+    // Continuar desde el punto de control (si no, se empieza de cero).
+    pub resume: bool,
+    // Líneas entre dos puntos de control.
+    pub checkpoint_every: u64,
+}
+
+impl Default for ResumeOptions {
+    fn default() -> ResumeOptions {
+        ResumeOptions {
+            resume: false,
+            checkpoint_every: 1_000_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeReport {
+    // El punto de control desde el que se ha reanudado, si lo había.
+    pub resumed: Option<Checkpoint>,
+    // El último punto de control: el final del fichero.
+    pub checkpoint: Checkpoint,
+}
+
+// Añade un texto precedido de su longitud: así `"ab" + ""` y `"a" + "b"` no se confunden.
+fn push_text(bytes: &mut Vec<u8>, text: &str) {
+    bytes.extend_from_slice(&(text.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(text.as_bytes());
+}
+
+impl FizzBuzzRules {
+    // Un resumen de las reglas (FNV-1a de 64 bits), o `None` si hay reglas con cierres,
+    // que no se pueden comparar. Se calcula sobre una codificación fija (enteros en
+    // little-endian, textos con su longitud y una etiqueta por variante), no sobre
+    // `{:?}`, cuyo formato puede cambiar: así es estable entre ejecuciones, máquinas y
+    // versiones de Rust. Si se añaden variantes, sus etiquetas deben ser nuevas.
+    pub fn rules_hash(&self) -> Option<u64> {
+        let mut bytes = Vec::new();
+        for rule in self.rules() {
+            let (divisor, word) = rule.divisor_word()?;
+            bytes.push(b'r');
+            bytes.extend_from_slice(&divisor.to_le_bytes());
+            bytes.extend_from_slice(&rule.priority().to_le_bytes());
+            bytes.push(match rule.mode() {
+                RuleMode::Concat => b'c',
+                RuleMode::Override => b'o',
+            });
+            push_text(&mut bytes, word);
+        }
+
+        bytes.push(b's');
+        push_text(&mut bytes, self.separator());
+        match self.fallback() {
+            Fallback::Number => bytes.push(b'n'),
+            Fallback::Text(text) => {
+                bytes.push(b't');
+                push_text(&mut bytes, text);
+            }
+        }
+        bytes.push(match self.number_format() {
+            NumberFormat::Decimal => b'd',
+            NumberFormat::Binary => b'b',
+            NumberFormat::Octal => b'o',
+            NumberFormat::Hex => b'x',
+            NumberFormat::Roman => b'r',
+            NumberFormat::Spanish => b's',
+            NumberFormat::English => b'e',
+        });
+
+        let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Some(hash)
+    }
+}
+
+// Un `Write` que cuenta los bytes que pasan por él.
+struct CountingWriter<W> {
+    inner: W,
+    bytes: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+// Comprueba que las últimas líneas antes de `checkpoint.byte_offset` son las que
+// corresponden a las reglas.
+fn verify_tail(
+    file: &mut File,
+    rules: &FizzBuzzRules,
+    checkpoint: &Checkpoint,
+) -> Result<(), ResumeError> {
+    let first = checkpoint.last_n - checkpoint.last_n.min(TAIL_LINES) + 1;
+    let mut expected = Vec::new();
+    for n in first..=checkpoint.last_n {
+        rules.write_fizzbuzz(&mut expected, n)?;
+    }
+    // Si no empieza en la línea 1, delante tiene que haber un salto de línea.
+    if first > 1 {
+        expected.insert(0, b'\n');
+    }
+
+    let len = expected.len() as u64;
+    if len > checkpoint.byte_offset || (first == 1 && len != checkpoint.byte_offset) {
+        return Err(ResumeError::Corrupt(crate::message!(
+            "resume.corrupt.offset"
+        )));
+    }
+
+    let mut actual = vec![0; expected.len()];
+    file.seek(SeekFrom::Start(checkpoint.byte_offset - len))?;
+    file.read_exact(&mut actual)?;
+
+    // La línea que falla: la que contiene el primer byte distinto.
+    match expected.iter().zip(&actual).position(|(a, b)| a != b) {
+        None => Ok(()),
+        Some(i) => {
+            let lines_before = expected[..i].iter().filter(|&&byte| byte == b'\n').count() as u64;
+            let line = first + lines_before - u64::from(first > 1);
+            Err(ResumeError::TailMismatch { line })
+        }
+    }
+}
+
+// Escribe las líneas de 1 a `n` en `path`, con puntos de control para poder reanudar
+// (con `options.resume`) si se interrumpe. Con las reglas clásicas usa el generador
+// rápido.
+pub fn write_fizzbuzz_resumable(
+    path: impl AsRef<Path>,
+    rules: &FizzBuzzRules,
+    n: u64,
+    options: &ResumeOptions,
+) -> Result<ResumeReport, ResumeError> {
+    let path = path.as_ref();
+    let sidecar = Checkpoint::path_for(path);
+    let rules_hash = rules.rules_hash().ok_or(ResumeError::NotResumable)?;
+
+    let resumed = match options.resume {
+        true => Checkpoint::read(&sidecar)?,
+        false => None,
+    };
+
+    let (file, mut checkpoint) = match resumed {
+        Some(checkpoint) => {
+            if checkpoint.rules_hash != rules_hash {
+                return Err(ResumeError::RulesChanged {
+                    expected: checkpoint.rules_hash,
+                    found: rules_hash,
+                });
+            }
+            if checkpoint.last_n > n {
+                return Err(ResumeError::BeyondEnd {
+                    last_n: checkpoint.last_n,
+                    n,
+                });
+            }
+
+            let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+            if file.metadata()?.len() < checkpoint.byte_offset {
+                return Err(ResumeError::Corrupt(crate::message!(
+                    "resume.corrupt.short"
+                )));
+            }
+            verify_tail(&mut file, rules, &checkpoint)?;
+
+            // Lo escrito después del punto de control puede estar a medias: fuera.
+            file.set_len(checkpoint.byte_offset)?;
+            file.seek(SeekFrom::Start(checkpoint.byte_offset))?;
+            (file, checkpoint)
+        }
+        None => {
+            // Sin punto de control solo se empieza de cero si no se pierde nada.
+            if options.resume && fs::metadata(path).is_ok_and(|meta| meta.len() > 0) {
+                return Err(ResumeError::NoCheckpoint(path.to_owned()));
+            }
+
+            // Primero el punto de control vacío y después se vacía el fichero: si el
+            // proceso se interrumpe entre medias, el punto de control antiguo ya no
+            // describe un fichero que ha dejado de existir, y "cero líneas" siempre
+            // es cierto (al reanudar se descarta lo que haya).
+            let checkpoint = Checkpoint {
+                last_n: 0,
+                byte_offset: 0,
+                rules_hash,
+            };
+            checkpoint.write(&sidecar)?;
+            let file = File::create(path)?;
+            (file, checkpoint)
+        }
+    };
+
+    // El resumen solo sirve para validar el punto de control: una colisión no debe
+    // bastar para elegir el generador rápido.
+    let fast = rules.is_classic();
+    let every = options.checkpoint_every.max(1);
+    let mut out = CountingWriter {
+        inner: BufWriter::with_capacity(1 << 20, file),
+        bytes: 0,
+    };
+
+    while checkpoint.last_n < n {
+        let (start, end) = (
+            checkpoint.last_n + 1,
+            n.min(checkpoint.last_n.saturating_add(every)),
+        );
+        if fast {
+            write_fizzbuzz_range_fast(&mut out, start..=end)?;
+        } else {
+            for i in start..=end {
+                rules.write_fizzbuzz(&mut out, i)?;
+            }
+        }
+
+        // Primero los datos en el disco y después el punto de control que los cubre.
+        out.flush()?;
+        out.inner.get_ref().sync_data()?;
+        checkpoint.last_n = end;
+        checkpoint.byte_offset += mem::take(&mut out.bytes);
+        checkpoint.write(&sidecar)?;
+    }
+
+    Ok(ResumeReport {
+        resumed,
+        checkpoint,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{byte_offset_of_line, Rule};
+
+    // Un directorio temporal propio de cada prueba (se borra al terminar).
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = std::env::temp_dir().join(format!(
+                "fizzbuzz-resume-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn file(&self) -> PathBuf {
+            self.0.join("salida.txt")
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn expected(rules: &FizzBuzzRules, n: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        rules.write_fizzbuzz_to(&mut out, n).unwrap();
+        out
+    }
+
+    fn options(resume: bool) -> ResumeOptions {
+        ResumeOptions {
+            resume,
+            checkpoint_every: 100,
+        }
+    }
+
+    fn checkpoint_at(rules: &FizzBuzzRules, last_n: u64) -> Checkpoint {
+        Checkpoint {
+            last_n,
+            byte_offset: expected(rules, last_n).len() as u64,
+            rules_hash: rules.rules_hash().unwrap(),
+        }
+    }
+
+    #[test]
+    fn writes_output_and_checkpoint() {
+        let dir = TempDir::new("writes");
+        let rules = FizzBuzzRules::classic();
+        let report = write_fizzbuzz_resumable(dir.file(), &rules, 1_234, &options(false)).unwrap();
+
+        assert_eq!(fs::read(dir.file()).unwrap(), expected(&rules, 1_234));
+        assert_eq!(report.resumed, None);
+        assert_eq!(report.checkpoint, checkpoint_at(&rules, 1_234));
+        let sidecar = Checkpoint::read(&Checkpoint::path_for(&dir.file())).unwrap();
+        assert_eq!(sidecar, Some(report.checkpoint));
+    }
+
+    #[test]
+    fn resumes_after_truncated_tail() {
+        let dir = TempDir::new("truncated");
+        let sidecar = Checkpoint::path_for(&dir.file());
+        // Reglas sin generador rápido, para probar también el camino lento.
+        let rules = FizzBuzzRules::classic().rule(7, "bazz").with_separator("-");
+        write_fizzbuzz_resumable(dir.file(), &rules, 1_000, &options(false)).unwrap();
+
+        // Simulamos una interrupción: el último punto de control es el de la línea 600
+        // y después se llegó a escribir media línea.
+        checkpoint_at(&rules, 600).write(&sidecar).unwrap();
+        let mut output = expected(&rules, 600);
+        output.extend_from_slice(b"601\nfi");
+        fs::write(dir.file(), output).unwrap();
+
+        let report = write_fizzbuzz_resumable(dir.file(), &rules, 2_000, &options(true)).unwrap();
+        assert_eq!(report.resumed, Some(checkpoint_at(&rules, 600)));
+        assert_eq!(fs::read(dir.file()).unwrap(), expected(&rules, 2_000));
+    }
+
+    #[test]
+    fn resumes_after_crash_before_truncating() {
+        // Al empezar de cero se guarda el punto de control vacío antes de vaciar el
+        // fichero: si se interrumpe justo entre medias, se reanuda desde cero.
+        let dir = TempDir::new("restart");
+        let rules = FizzBuzzRules::classic();
+        fs::write(dir.file(), expected(&rules, 500)).unwrap();
+        checkpoint_at(&rules, 0)
+            .write(&Checkpoint::path_for(&dir.file()))
+            .unwrap();
+
+        write_fizzbuzz_resumable(dir.file(), &rules, 300, &options(true)).unwrap();
+        assert_eq!(fs::read(dir.file()).unwrap(), expected(&rules, 300));
+    }
+
+    #[test]
+    fn rejects_changed_rules() {
+        let dir = TempDir::new("rules");
+        let classic = FizzBuzzRules::classic();
+        write_fizzbuzz_resumable(dir.file(), &classic, 500, &options(false)).unwrap();
+
+        let other = FizzBuzzRules::classic().rule(7, "bazz");
+        let err = write_fizzbuzz_resumable(dir.file(), &other, 1_000, &options(true)).unwrap_err();
+        assert!(matches!(
+            err,
+            ResumeError::RulesChanged { expected, found }
+                if expected == classic.rules_hash().unwrap()
+                    && found == other.rules_hash().unwrap()
+        ));
+        // Nada se ha tocado.
+        assert_eq!(fs::read(dir.file()).unwrap(), expected(&classic, 500));
+    }
+
+    #[test]
+    fn rejects_inconsistent_files() {
+        let dir = TempDir::new("inconsistent");
+        let sidecar = Checkpoint::path_for(&dir.file());
+        let rules = FizzBuzzRules::classic();
+        write_fizzbuzz_resumable(dir.file(), &rules, 500, &options(false)).unwrap();
+
+        // Más allá de lo pedido.
+        let err = write_fizzbuzz_resumable(dir.file(), &rules, 400, &options(true)).unwrap_err();
+        assert!(matches!(
+            err,
+            ResumeError::BeyondEnd {
+                last_n: 500,
+                n: 400
+            }
+        ));
+
+        // Una línea cambiada antes del punto de control.
+        let mut output = expected(&rules, 500);
+        let at = byte_offset_of_line(490) as usize;
+        output[at] = b'x';
+        fs::write(dir.file(), &output).unwrap();
+        let err = write_fizzbuzz_resumable(dir.file(), &rules, 600, &options(true)).unwrap_err();
+        assert!(matches!(err, ResumeError::TailMismatch { line: 490 }));
+
+        // El fichero es más corto que el punto de control.
+        fs::write(dir.file(), expected(&rules, 450)).unwrap();
+        let err = write_fizzbuzz_resumable(dir.file(), &rules, 600, &options(true)).unwrap_err();
+        assert!(matches!(err, ResumeError::Corrupt(_)));
+
+        // Sin punto de control no se sobrescribe un fichero con datos.
+        fs::remove_file(&sidecar).unwrap();
+        let err = write_fizzbuzz_resumable(dir.file(), &rules, 600, &options(true)).unwrap_err();
+        assert!(matches!(err, ResumeError::NoCheckpoint(_)));
+
+        fs::write(&sidecar, "fizzbuzz-checkpoint 1\nn=10\n").unwrap();
+        let err = write_fizzbuzz_resumable(dir.file(), &rules, 600, &options(true)).unwrap_err();
+        assert!(matches!(err, ResumeError::Corrupt(_)));
+    }
+
+    #[test]
+    fn fast_path_depends_on_the_rules_not_the_hash() {
+        assert!(FizzBuzzRules::classic().is_classic());
+        assert!(FizzBuzzRules::new()
+            .rule(3, "fizz")
+            .rule(5, "buzz")
+            .is_classic());
+        for rules in [
+            FizzBuzzRules::new().rule(5, "buzz").rule(3, "fizz"),
+            FizzBuzzRules::classic().with_separator("-"),
+            FizzBuzzRules::classic().with_fallback(Fallback::Text(String::new())),
+            FizzBuzzRules::classic().with_number_format(NumberFormat::Roman),
+            FizzBuzzRules::classic().rule(7, "bazz"),
+            FizzBuzzRules::new()
+                .rule(3, "fizz")
+                .with(Rule::divisible_by(5, "buzz").with_mode(RuleMode::Override)),
+            FizzBuzzRules::new().rule(3, "fizz"),
+        ] {
+            assert!(!rules.is_classic(), "{rules:?}");
+        }
+
+        // Unas reglas que no son las clásicas se escriben con ellas, no con el
+        // generador rápido, aunque se reanuden.
+        let dir = TempDir::new("not-classic");
+        let rules = FizzBuzzRules::new().rule(5, "buzz").rule(3, "fizz");
+        write_fizzbuzz_resumable(dir.file(), &rules, 300, &options(false)).unwrap();
+        assert_eq!(fs::read(dir.file()).unwrap(), expected(&rules, 300));
+    }
+
+    #[test]
+    fn rules_hash_is_stable() {
+        // Fijado: si cambia, los puntos de control ya guardados dejan de servir.
+        assert_eq!(
+            FizzBuzzRules::classic().rules_hash(),
+            Some(0xc371_f556_80f0_6cfe)
+        );
+
+        let hashes = [
+            FizzBuzzRules::classic(),
+            FizzBuzzRules::new().rule(3, "fiz").rule(5, "zbuzz"),
+            FizzBuzzRules::new().rule(5, "buzz").rule(3, "fizz"),
+            FizzBuzzRules::classic().with_separator("-"),
+            FizzBuzzRules::classic().with_fallback(Fallback::Text(String::new())),
+            FizzBuzzRules::classic().with_number_format(NumberFormat::Roman),
+            FizzBuzzRules::new()
+                .rule(3, "fizz")
+                .with(Rule::divisible_by(5, "buzz").with_mode(RuleMode::Override)),
+        ]
+        .map(|rules| rules.rules_hash().unwrap());
+        for (i, a) in hashes.iter().enumerate() {
+            assert!(hashes[i + 1..].iter().all(|b| a != b), "{hashes:x?}");
+        }
+
+        let closures = FizzBuzzRules::classic().with(Rule::when(|n| n == 7, "seven"));
+        assert_eq!(closures.rules_hash(), None);
+    }
+}
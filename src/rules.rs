@@ -0,0 +1,484 @@
+// Reglas configurables de FizzBuzz.
+// =============================================================
+// Una regla decide si un número `n` produce una palabra. Puede ser:
+// • de divisibilidad: "si `divisor` divide a `n`, escribe `word`";
+// • un cierre cualquiera `Fn(u64) -> Option<Cow<str>>`, p.ej. "contiene el dígito 3",
+//   "es primo", "es un cuadrado perfecto" o "es capicúa".
+//
+// Las reglas se evalúan por orden de prioridad ascendente (y, a igual prioridad,
+// en el orden en el que se añadieron). Cada regla que coincide:
+// • con `RuleMode::Concat` añade su palabra a la de las reglas anteriores, por lo que
+//   el caso `15` ("fizzbuzz") no necesita tratamiento especial;
+// • con `RuleMode::Override` sustituye las palabras de todas las reglas anteriores
+//   (las de menor prioridad); las de mayor prioridad aún pueden añadir la suya.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::{Divisor, NumberFormat};
+
+// Un cierre que decide la palabra de `n` (o `None` si la regla no coincide).
+// Es `Send + Sync` para poder compartir las reglas entre hilos.
+pub type RuleFn = Box<dyn Fn(u64) -> Option<Cow<'static, str>> + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuleMode {
+    // La palabra se concatena con las anteriores.
+    #[default]
+    Concat,
+    // La palabra sustituye a las anteriores.
+    Override,
+}
+
+enum Matcher {
+    // El divisor con su inverso precalculado: se comprueba sin dividir.
+    Divisor { divisor: Divisor, word: String },
+    Custom(RuleFn),
+}
+
+pub struct Rule {
+    matcher: Matcher,
+    priority: i32,
+    mode: RuleMode,
+}
+
+impl Rule {
+    // "Si `divisor` divide a `n`, escribe `word`". Dividir por `0` nunca coincide.
+    pub fn divisible_by(divisor: u64, word: impl Into<String>) -> Rule {
+        Rule::from_matcher(Matcher::Divisor {
+            divisor: Divisor::new(divisor),
+            word: word.into(),
+        })
+    }
+
+    // Una regla arbitraria a partir de un cierre.
+    pub fn custom<F>(f: F) -> Rule
+    where
+        F: Fn(u64) -> Option<Cow<'static, str>> + Send + Sync + 'static,
+    {
+        Rule::from_matcher(Matcher::Custom(Box::new(f)))
+    }
+
+    // Atajo para el caso habitual: un predicado y una palabra fija, p.ej.
+    // `Rule::when(is_prime, "prime")`.
+    pub fn when<P>(predicate: P, word: impl Into<String>) -> Rule
+    where
+        P: Fn(u64) -> bool + Send + Sync + 'static,
+    {
+        let word: Cow<'static, str> = Cow::Owned(word.into());
+        Rule::custom(move |n| predicate(n).then(|| word.clone()))
+    }
+
+    fn from_matcher(matcher: Matcher) -> Rule {
+        Rule {
+            matcher,
+            priority: 0,
+            mode: RuleMode::Concat,
+        }
+    }
+
+    // Cambia la prioridad (por defecto `0`; las mayores se evalúan después).
+    pub fn with_priority(mut self, priority: i32) -> Rule {
+        self.priority = priority;
+        self
+    }
+
+    // Cambia el modo de combinación (por defecto `RuleMode::Concat`).
+    pub fn with_mode(mut self, mode: RuleMode) -> Rule {
+        self.mode = mode;
+        self
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn mode(&self) -> RuleMode {
+        self.mode
+    }
+
+    // El divisor, si es una regla de divisibilidad.
+    pub fn divisor(&self) -> Option<u64> {
+        match self.matcher {
+            Matcher::Divisor { divisor, .. } => Some(divisor.get()),
+            Matcher::Custom(_) => None,
+        }
+    }
+
+    // El divisor y la palabra, si es una regla de divisibilidad.
+    pub(crate) fn divisor_word(&self) -> Option<(u64, &str)> {
+        match &self.matcher {
+            Matcher::Divisor { divisor, word } => Some((divisor.get(), word)),
+            Matcher::Custom(_) => None,
+        }
+    }
+
+    // La palabra que esta regla produce para `n`, si coincide.
+    pub fn word(&self, n: u64) -> Option<Cow<'_, str>> {
+        match &self.matcher {
+            Matcher::Divisor { divisor, word } => {
+                divisor.divides(n).then_some(Cow::Borrowed(word.as_str()))
+            }
+            Matcher::Custom(f) => f(n),
+        }
+    }
+}
+
+// Los cierres no implementan `Debug`, así que lo escribimos a mano.
+impl fmt::Debug for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("Rule");
+        match &self.matcher {
+            Matcher::Divisor { divisor, word } => {
+                debug.field("divisor", &divisor.get()).field("word", word);
+            }
+            Matcher::Custom(_) => {
+                debug.field("custom", &format_args!("<cierre>"));
+            }
+        }
+        debug
+            .field("priority", &self.priority)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+// Predicados de ejemplo para usar con `Rule::when`.
+
+// ¿Aparece el dígito `digit` en la representación decimal de `n`?
+pub fn contains_digit(n: u64, digit: u8) -> bool {
+    let digit = u64::from(digit);
+    let mut n = n;
+    loop {
+        if n % 10 == digit {
+            return true;
+        }
+        n /= 10;
+        if n == 0 {
+            return false;
+        }
+    }
+}
+
+// ¿Es `n` un cuadrado perfecto?
+pub fn is_perfect_square(n: u64) -> bool {
+    let root = n.isqrt();
+    root * root == n
+}
+
+// ¿Se lee igual `n` de izquierda a derecha que de derecha a izquierda?
+pub fn is_palindrome(n: u64) -> bool {
+    let mut reversed = 0u128;
+    let mut rest = n;
+    while rest > 0 {
+        reversed = reversed * 10 + u128::from(rest % 10);
+        rest /= 10;
+    }
+    reversed == u128::from(n)
+}
+
+// Qué se escribe cuando ninguna regla coincide.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Fallback {
+    // El propio número, como en el FizzBuzz tradicional.
+    #[default]
+    Number,
+    // Un texto fijo (posiblemente vacío).
+    Text(String),
+}
+
+// Un conjunto ordenado de reglas.
+#[derive(Debug)]
+pub struct FizzBuzzRules {
+    rules: Vec<Rule>,
+    // Lo que se escribe entre dos palabras concatenadas (por defecto, nada).
+    separator: String,
+    fallback: Fallback,
+    // Cómo se escribe el número con `Fallback::Number` (por defecto, en decimal).
+    number_format: NumberFormat,
+}
+
+impl FizzBuzzRules {
+    // Un conjunto de reglas vacío: todos los números se imprimen tal cual.
+    pub fn new() -> FizzBuzzRules {
+        FizzBuzzRules {
+            rules: Vec::new(),
+            separator: String::new(),
+            fallback: Fallback::Number,
+            number_format: NumberFormat::Decimal,
+        }
+    }
+
+    // Las reglas clásicas: 3 = "fizz", 5 = "buzz".
+    pub fn classic() -> FizzBuzzRules {
+        FizzBuzzRules::new().rule(3, "fizz").rule(5, "buzz")
+    }
+
+    // Añade una regla de divisibilidad (estilo `builder`), p.ej.:
+    // `FizzBuzzRules::classic().rule(7, "bazz").rule(11, "fuzz")`
+    pub fn rule(mut self, divisor: u64, word: impl Into<String>) -> FizzBuzzRules {
+        self.push(divisor, word);
+        self
+    }
+
+    // Igual que `rule`, pero sobre una instancia mutable.
+    pub fn push(&mut self, divisor: u64, word: impl Into<String>) {
+        self.push_rule(Rule::divisible_by(divisor, word));
+    }
+
+    // Añade cualquier regla, p.ej.:
+    // `FizzBuzzRules::classic().with(Rule::when(is_prime, "prime").with_mode(RuleMode::Override))`
+    pub fn with(mut self, rule: Rule) -> FizzBuzzRules {
+        self.push_rule(rule);
+        self
+    }
+
+    // Las reglas se mantienen ordenadas por prioridad; a igual prioridad, la nueva
+    // va detrás de las que ya había.
+    pub fn push_rule(&mut self, rule: Rule) {
+        let i = self
+            .rules
+            .partition_point(|other| other.priority <= rule.priority);
+        self.rules.insert(i, rule);
+    }
+
+    // Cambia el separador entre palabras concatenadas, p.ej. `"-"` para "fizz-buzz".
+    pub fn with_separator(mut self, separator: impl Into<String>) -> FizzBuzzRules {
+        self.separator = separator.into();
+        self
+    }
+
+    // Cambia lo que se escribe cuando ninguna regla coincide.
+    pub fn with_fallback(mut self, fallback: Fallback) -> FizzBuzzRules {
+        self.fallback = fallback;
+        self
+    }
+
+    // Cambia cómo se escriben los números, p.ej. `NumberFormat::Roman` para "XIV".
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> FizzBuzzRules {
+        self.number_format = number_format;
+        self
+    }
+
+    // Las reglas en el orden en el que se evalúan.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    pub fn fallback(&self) -> &Fallback {
+        &self.fallback
+    }
+
+    pub fn number_format(&self) -> NumberFormat {
+        self.number_format
+    }
+
+    // ¿Escriben estas reglas exactamente lo mismo que `FizzBuzzRules::classic()`?
+    // (Es lo que permite usar los generadores rápidos, que solo saben hacer eso.)
+    pub fn is_classic(&self) -> bool {
+        let classic = |rule: &Rule, divisor, word| {
+            rule.mode == RuleMode::Concat && rule.divisor_word() == Some((divisor, word))
+        };
+        matches!(&self.rules[..], [fizz, buzz] if classic(fizz, 3, "fizz") && classic(buzz, 5, "buzz"))
+            && self.separator.is_empty()
+            && self.fallback == Fallback::Number
+            && self.number_format == NumberFormat::Decimal
+    }
+
+    // Combina las palabras de todas las reglas que coinciden con `n`, o devuelve
+    // `None` si ninguna coincide (y hay que escribir el `Fallback`).
+    // Si coincide una sola regla, se presta su palabra sin copiarla.
+    pub fn word(&self, n: u64) -> Option<Cow<'_, str>> {
+        self.combine(
+            self.rules
+                .iter()
+                .filter_map(|rule| rule.word(n).map(|word| (rule.mode, word))),
+        )
+    }
+
+    // Combina, en orden de evaluación, las palabras de las reglas que han coincidido.
+    pub(crate) fn combine<'a>(
+        &'a self,
+        matched: impl IntoIterator<Item = (RuleMode, Cow<'a, str>)>,
+    ) -> Option<Cow<'a, str>> {
+        let mut word: Option<Cow<'a, str>> = None;
+
+        for (mode, rule_word) in matched {
+            word = match (mode, word) {
+                (RuleMode::Override, _) | (RuleMode::Concat, None) => Some(rule_word),
+                (RuleMode::Concat, Some(prev)) => {
+                    Some(Cow::Owned(prev.into_owned() + &self.separator + &rule_word))
+                }
+            };
+        }
+
+        word.filter(|word| !word.is_empty())
+    }
+
+    // El "token" que corresponde a `n`: la palabra de las reglas o el `Fallback`.
+    pub fn token(&self, n: u64) -> Cow<'_, str> {
+        match (self.word(n), &self.fallback) {
+            (Some(word), _) => word,
+            (None, Fallback::Number) => Cow::Owned(self.number_format.format(n)),
+            (None, Fallback::Text(text)) => Cow::Borrowed(text),
+        }
+    }
+
+    // Escribe la línea de `n` en cualquier `Write` en lugar de en `stdout`.
+    pub fn write_fizzbuzz<W: Write>(&self, out: &mut W, n: u64) -> io::Result<()> {
+        match (self.word(n), &self.fallback) {
+            (Some(word), _) => writeln!(out, "{}", word),
+            (None, Fallback::Number) => writeln!(out, "{}", self.number_format.display(n)),
+            (None, Fallback::Text(text)) => writeln!(out, "{}", text),
+        }
+    }
+
+    pub fn write_fizzbuzz_to<W: Write>(&self, out: &mut W, n: u64) -> io::Result<()> {
+        for n in 1..=n {
+            self.write_fizzbuzz(out, n)?;
+        }
+        Ok(())
+    }
+
+    pub fn fizzbuzz(&self, n: u64) {
+        self.write_fizzbuzz(&mut io::stdout().lock(), n)
+            .expect("error al escribir en `stdout`");
+    }
+
+    pub fn fizzbuzz_to(&self, n: u64) {
+        self.write_fizzbuzz_to(&mut io::stdout().lock(), n)
+            .expect("error al escribir en `stdout`");
+    }
+}
+
+// Por defecto se usan las reglas clásicas.
+impl Default for FizzBuzzRules {
+    fn default() -> FizzBuzzRules {
+        FizzBuzzRules::classic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn divisors(rules: &FizzBuzzRules) -> Vec<Option<u64>> {
+        rules.rules().iter().map(Rule::divisor).collect()
+    }
+
+    #[test]
+    fn concatenates_in_order() {
+        let rules = FizzBuzzRules::classic().rule(7, "bazz").rule(11, "fuzz");
+        assert_eq!(rules.token(1155), "fizzbuzzbazzfuzz");
+        assert_eq!(rules.token(77), "bazzfuzz");
+        assert_eq!(rules.token(21), "fizzbazz");
+        assert_eq!(rules.token(13), "13");
+        assert_eq!(rules.word(13), None);
+        // Una sola regla: se presta la palabra sin copiarla.
+        assert!(matches!(rules.word(3), Some(Cow::Borrowed("fizz"))));
+    }
+
+    #[test]
+    fn separator_and_fallback() {
+        let rules = FizzBuzzRules::classic()
+            .with_separator("-")
+            .with_fallback(Fallback::Text(".".to_owned()));
+        let mut out = Vec::new();
+        rules.write_fizzbuzz_to(&mut out, 15).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            ".\n.\nfizz\n.\nbuzz\nfizz\n.\n.\nfizz\nbuzz\n.\nfizz\n.\n.\nfizz-buzz\n"
+        );
+
+        let roman = FizzBuzzRules::classic().with_number_format(NumberFormat::Roman);
+        assert_eq!(roman.token(14), "XIV");
+        assert_eq!(FizzBuzzRules::new().token(15), "15");
+    }
+
+    #[test]
+    fn equal_priorities_keep_insertion_order() {
+        let mut rules = FizzBuzzRules::new()
+            .with(Rule::divisible_by(2, "a").with_priority(1))
+            .with(Rule::divisible_by(3, "b").with_priority(1))
+            .with(Rule::divisible_by(5, "c"));
+        rules.push_rule(Rule::divisible_by(7, "d").with_priority(1));
+        rules.push_rule(Rule::divisible_by(11, "e").with_priority(-1));
+
+        assert_eq!(
+            divisors(&rules),
+            [Some(11), Some(5), Some(2), Some(3), Some(7)]
+        );
+        assert_eq!(rules.token(2 * 3 * 5 * 7 * 11), "ecabd");
+    }
+
+    #[test]
+    fn override_replaces_lower_words_and_later_rules_append() {
+        let rules = FizzBuzzRules::classic()
+            .with(
+                Rule::when(crate::is_prime, "prime")
+                    .with_priority(1)
+                    .with_mode(RuleMode::Override),
+            )
+            .with(Rule::when(|n| contains_digit(n, 7), "seven").with_priority(2));
+
+        assert_eq!(rules.token(5), "prime");
+        assert_eq!(rules.token(2), "prime");
+        assert_eq!(rules.token(37), "primeseven");
+        assert_eq!(rules.token(57), "fizzseven");
+        assert_eq!(rules.token(15), "fizzbuzz");
+        assert_eq!(rules.token(70), "buzzseven");
+        assert_eq!(rules.rules()[2].mode(), RuleMode::Override);
+    }
+
+    #[test]
+    fn custom_rules_can_borrow_or_skip() {
+        let rules = FizzBuzzRules::new()
+            .with(Rule::custom(|n| {
+                (n % 4 == 0).then_some(Cow::Borrowed("four"))
+            }))
+            .with_separator("+")
+            .rule(6, "six");
+        assert_eq!(rules.token(12), "four+six");
+        assert_eq!(rules.rules()[0].divisor(), None);
+    }
+
+    #[test]
+    fn contains_digit_edges() {
+        assert!(contains_digit(0, 0));
+        assert!(!contains_digit(0, 1));
+        assert!(contains_digit(10, 0));
+        assert!(contains_digit(u64::MAX, 0));
+        assert!(contains_digit(u64::MAX, 9));
+        // 18446744073709551615 no tiene ningún 2.
+        assert!(!contains_digit(u64::MAX, 2));
+    }
+
+    #[test]
+    fn is_perfect_square_edges() {
+        assert!(is_perfect_square(0));
+        assert!(is_perfect_square(1));
+        assert!(!is_perfect_square(2));
+        let root = u64::from(u32::MAX);
+        assert!(is_perfect_square(root * root));
+        assert!(!is_perfect_square(root * root - 1));
+        assert!(!is_perfect_square(root * root + 1));
+        assert!(is_perfect_square(1 << 62));
+        assert!(!is_perfect_square(u64::MAX));
+    }
+
+    #[test]
+    fn is_palindrome_edges() {
+        assert!((0..=9).all(is_palindrome));
+        assert!(!is_palindrome(10));
+        assert!(is_palindrome(11));
+        assert!(is_palindrome(12_321));
+        assert!(!is_palindrome(12_320));
+        assert!(is_palindrome(9_223_372_036_302_733_229));
+        assert!(!is_palindrome(u64::MAX));
+    }
+}
@@ -0,0 +1,702 @@
+// Figuras.
+// =============================================================
+// `Shape` reúne lo que tienen en común todas las figuras: área, perímetro,
+// traslación, caja envolvente, centroide y si contienen un punto. Así se pueden
+// mezclar figuras distintas en una misma escena (`Vec<Box<dyn Shape>>`) y tratarlas
+// igual gracias al despacho dinámico.
+//
+// Las figuras trabajan con `f64` (`Point` y `Rectangle` sin parámetro): con áreas de
+// círculos y elipses no tendría sentido usar enteros.
+//
+// Con `Shape::transformed` se les aplica una transformación afín (`Transform2D`).
+// El resultado no tiene por qué ser del mismo tipo: un rectángulo girado ya no tiene
+// los lados paralelos a los ejes (pasa a ser un `Polygon`) y un círculo estirado en
+// una sola dirección es una `Ellipse`.
+//
+// VER: https://doc.rust-lang.org/stable/rust-by-example/trait/dyn.html
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use crate::{Point, Rectangle, Transform2D, Vector2};
+
+pub trait Shape: fmt::Debug {
+    fn area(&self) -> f64;
+
+    fn perimeter(&self) -> f64;
+
+    fn translate(&mut self, x: f64, y: f64);
+
+    // El menor rectángulo (normalizado, con los lados paralelos a los ejes) que la contiene.
+    fn bounding_box(&self) -> Rectangle;
+
+    // El centro de masas de la figura (supuesta de densidad uniforme).
+    fn centroid(&self) -> Point;
+
+    // Los bordes forman parte de la figura.
+    fn contains(&self, point: Point) -> bool;
+
+    // La figura que resulta de aplicarle `transform`.
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape>;
+}
+
+impl Shape for Rectangle {
+    // Los métodos del rasgo llaman a los de `Rectangle` (los métodos propios de un
+    // tipo tienen prioridad sobre los de sus rasgos).
+    fn area(&self) -> f64 {
+        Rectangle::area(self)
+    }
+
+    fn perimeter(&self) -> f64 {
+        Rectangle::perimeter(self)
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        Rectangle::translate(self, x, y)
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        self.normalized()
+    }
+
+    fn centroid(&self) -> Point {
+        self.center()
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        self.contains_point(point)
+    }
+
+    // Sin giro ni cizalla sigue siendo un `Rectangle`; si no, las cuatro esquinas
+    // transformadas forman un paralelogramo (`Polygon`).
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape> {
+        let (p1, p2) = (self.p1, self.p2);
+        if transform.is_axis_aligned() {
+            return Box::new(Rectangle::new(transform.apply(p1), transform.apply(p2)));
+        }
+        let corners = [p1, Point::new(p2.x, p1.y), p2, Point::new(p1.x, p2.y)];
+        Box::new(Polygon {
+            vertices: corners.map(|corner| transform.apply(corner)).to_vec(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: Point, radius: f64) -> Circle {
+        Circle { center, radius }
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * PI * self.radius
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        self.center += Vector2::new(x, y);
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        let radius = Vector2::new(self.radius, self.radius);
+        Rectangle::new(self.center - radius, self.center + radius)
+    }
+
+    fn centroid(&self) -> Point {
+        self.center
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        self.center.distance_squared(point) <= self.radius * self.radius
+    }
+
+    // Solo las semejanzas conservan los círculos; el resto los convierte en elipses.
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape> {
+        match transform.similarity_scale() {
+            Some(scale) => Box::new(Circle::new(
+                transform.apply(self.center),
+                self.radius * scale,
+            )),
+            None => Ellipse::new(self.center, self.radius, self.radius).transformed(transform),
+        }
+    }
+}
+
+// Una elipse con semiejes `radius_x` y `radius_y`, girada `rotation` radianes (en
+// sentido antihorario) alrededor de su centro.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipse {
+    pub center: Point,
+    pub radius_x: f64,
+    pub radius_y: f64,
+    pub rotation: f64,
+}
+
+impl Ellipse {
+    // Con los ejes paralelos a los de coordenadas.
+    pub fn new(center: Point, radius_x: f64, radius_y: f64) -> Ellipse {
+        Ellipse {
+            center,
+            radius_x,
+            radius_y,
+            rotation: 0.0,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f64) -> Ellipse {
+        self.rotation = rotation;
+        self
+    }
+}
+
+impl Shape for Ellipse {
+    fn area(&self) -> f64 {
+        PI * self.radius_x * self.radius_y
+    }
+
+    // No tiene fórmula cerrada: usamos la segunda aproximación de Ramanujan, con un
+    // error relativo por debajo de 10⁻⁹ salvo en elipses muy alargadas.
+    fn perimeter(&self) -> f64 {
+        let (a, b) = (self.radius_x.abs(), self.radius_y.abs());
+        if a + b == 0.0 {
+            return 0.0;
+        }
+        let h = ((a - b) / (a + b)).powi(2);
+        PI * (a + b) * (1.0 + 3.0 * h / (10.0 + (4.0 - 3.0 * h).sqrt()))
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        self.center += Vector2::new(x, y);
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (a, b) = (self.radius_x, self.radius_y);
+        let half = Vector2::new(
+            (a * a * cos * cos + b * b * sin * sin).sqrt(),
+            (a * a * sin * sin + b * b * cos * cos).sqrt(),
+        );
+        Rectangle::new(self.center - half, self.center + half)
+    }
+
+    fn centroid(&self) -> Point {
+        self.center
+    }
+
+    fn contains(&self, point: Point) -> bool {
+        // Giramos el punto al revés para trabajar con la elipse sin girar.
+        let (sin, cos) = self.rotation.sin_cos();
+        let d = point - self.center;
+        let (u, v) = (d.x * cos + d.y * sin, -d.x * sin + d.y * cos);
+        (u / self.radius_x).powi(2) + (v / self.radius_y).powi(2) <= 1.0
+    }
+
+    // La elipse es la imagen de la circunferencia unidad por "escalar por los
+    // semiejes, girar y trasladar al centro". Añadiendo `transform` sigue siendo la
+    // imagen de la circunferencia unidad, y los valores singulares de la parte lineal
+    // dan los nuevos semiejes y su giro.
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape> {
+        let linear = Transform2D::scale(self.radius_x, self.radius_y)
+            .then(&Transform2D::rotate(self.rotation))
+            .then(transform);
+        let (radius_x, radius_y, rotation) = linear.singular_values();
+        Box::new(
+            Ellipse::new(transform.apply(self.center), radius_x, radius_y).with_rotation(rotation),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub a: Point,
+    pub b: Point,
+    pub c: Point,
+}
+
+impl Triangle {
+    pub fn new(a: Point, b: Point, c: Point) -> Triangle {
+        Triangle { a, b, c }
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        ((self.b - self.a).cross(self.c - self.a) / 2.0).abs()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.a.distance(self.b) + self.b.distance(self.c) + self.c.distance(self.a)
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        let offset = Vector2::new(x, y);
+        for vertex in [&mut self.a, &mut self.b, &mut self.c] {
+            *vertex += offset;
+        }
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box_of(&[self.a, self.b, self.c])
+    }
+
+    fn centroid(&self) -> Point {
+        let sum = self.a.to_vector() + self.b.to_vector() + self.c.to_vector();
+        Point::origin() + sum * (1.0 / 3.0)
+    }
+
+    // El punto está dentro si queda al mismo lado (o encima) de los tres lados,
+    // recorridos en orden. Si los vértices están alineados, los tres productos se
+    // anulan en toda la recta que los une: el triángulo es solo sus lados.
+    fn contains(&self, point: Point) -> bool {
+        if (self.b - self.a).cross(self.c - self.a) == 0.0 {
+            return [(self.a, self.b), (self.b, self.c), (self.c, self.a)]
+                .into_iter()
+                .any(|(p, q)| on_segment(p, q, point));
+        }
+
+        let side = |from: Point, to: Point| (to - from).cross(point - from);
+        let sides = [
+            side(self.a, self.b),
+            side(self.b, self.c),
+            side(self.c, self.a),
+        ];
+        sides.iter().all(|&s| s >= 0.0) || sides.iter().all(|&s| s <= 0.0)
+    }
+
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape> {
+        let [a, b, c] = [self.a, self.b, self.c].map(|vertex| transform.apply(vertex));
+        Box::new(Triangle::new(a, b, c))
+    }
+}
+
+// Un polígono simple (sus lados no se cruzan), con los vértices en orden, en
+// cualquier sentido.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    // `None` si tiene menos de tres vértices.
+    pub fn new(vertices: Vec<Point>) -> Option<Polygon> {
+        (vertices.len() >= 3).then_some(Polygon { vertices })
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    // Los lados, como pares de vértices consecutivos (el último se une con el primero).
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    // Área con signo (fórmula del área de Gauss): positiva si los vértices van en
+    // sentido antihorario.
+    pub fn signed_area(&self) -> f64 {
+        self.edges()
+            .map(|(p, q)| p.to_vector().cross(q.to_vector()))
+            .sum::<f64>()
+            / 2.0
+    }
+}
+
+impl Shape for Polygon {
+    fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    fn perimeter(&self) -> f64 {
+        self.edges().map(|(p, q)| p.distance(q)).sum()
+    }
+
+    fn translate(&mut self, x: f64, y: f64) {
+        let offset = Vector2::new(x, y);
+        for vertex in &mut self.vertices {
+            *vertex += offset;
+        }
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        bounding_box_of(&self.vertices)
+    }
+
+    // Suma de los centroides de los triángulos (origen, p, q), ponderados por su área
+    // con signo. Si el área es `0` (vértices alineados), la media de los vértices.
+    fn centroid(&self) -> Point {
+        let area = self.signed_area();
+        if area == 0.0 {
+            let sum = self
+                .vertices
+                .iter()
+                .fold(Vector2::zero(), |sum, vertex| sum + vertex.to_vector());
+            return Point::origin() + sum * (1.0 / self.vertices.len() as f64);
+        }
+
+        let weighted = self.edges().fold(Vector2::zero(), |sum, (p, q)| {
+            let (p, q) = (p.to_vector(), q.to_vector());
+            sum + (p + q) * p.cross(q)
+        });
+        Point::origin() + weighted * (1.0 / (6.0 * area))
+    }
+
+    // Regla par–impar: una semirrecta horizontal desde el punto cruza el borde un
+    // número impar de veces si está dentro. Los puntos del borde cuentan como dentro.
+    fn contains(&self, point: Point) -> bool {
+        let mut inside = false;
+        for (p, q) in self.edges() {
+            if on_segment(p, q, point) {
+                return true;
+            }
+            if (p.y > point.y) != (q.y > point.y) {
+                let x = p.x + (point.y - p.y) / (q.y - p.y) * (q.x - p.x);
+                if point.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    // Las transformaciones afines conservan las rectas: basta con mover los vértices.
+    fn transformed(&self, transform: &Transform2D) -> Box<dyn Shape> {
+        Box::new(Polygon {
+            vertices: self.vertices.iter().map(|&v| transform.apply(v)).collect(),
+        })
+    }
+}
+
+// ¿Está `point` en el segmento de `p` a `q`? (En su recta y dentro de su caja.)
+fn on_segment(p: Point, q: Point, point: Point) -> bool {
+    (q - p).cross(point - p) == 0.0 && Rectangle::new(p, q).contains_point(point)
+}
+
+fn bounding_box_of(points: &[Point]) -> Rectangle {
+    let first = Rectangle::new(points[0], points[0]);
+    points[1..].iter().fold(first, |bounds, &point| {
+        bounds.union(&Rectangle::new(point, point))
+    })
+}
+
+// Escenas.
+
+// Figuras de cualquier tipo, en el orden en que se dibujan.
+#[derive(Debug, Default)]
+pub struct Scene {
+    pub shapes: Vec<Box<dyn Shape>>,
+}
+
+// Dos figuras que se solapan (índices en `Scene::shapes`) y el área común estimada.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overlap {
+    pub first: usize,
+    pub second: usize,
+    pub area: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SceneReport {
+    // Suma de las áreas de las figuras (las zonas solapadas cuentan varias veces).
+    pub total_area: f64,
+    // Área cubierta por alguna figura (estimada).
+    pub covered_area: f64,
+    pub overlaps: Vec<Overlap>,
+}
+
+impl Scene {
+    pub fn new() -> Scene {
+        Scene::default()
+    }
+
+    // Añade una figura (estilo `builder`).
+    pub fn with(mut self, shape: impl Shape + 'static) -> Scene {
+        self.push(shape);
+        self
+    }
+
+    pub fn push(&mut self, shape: impl Shape + 'static) {
+        self.shapes.push(Box::new(shape));
+    }
+
+    // Aplica `transform` a todas las figuras (algunas pueden cambiar de tipo).
+    pub fn transform(&mut self, transform: &Transform2D) {
+        for shape in &mut self.shapes {
+            *shape = shape.transformed(transform);
+        }
+    }
+
+    pub fn total_area(&self) -> f64 {
+        self.shapes.iter().map(|shape| shape.area()).sum()
+    }
+
+    // La caja que contiene a todas las figuras, o `None` si la escena está vacía.
+    pub fn bounding_box(&self) -> Option<Rectangle> {
+        self.shapes
+            .iter()
+            .map(|shape| shape.bounding_box())
+            .reduce(|a, b| a.union(&b))
+    }
+
+    // Las áreas de solapamiento entre figuras de formas cualesquiera no tienen una
+    // fórmula general, así que se estiman con una rejilla de `resolution` ×
+    // `resolution` puntos sobre la zona común de las cajas envolventes (el error
+    // disminuye al aumentar `resolution`). Solo se informa de los solapamientos con
+    // área positiva.
+    pub fn report(&self, resolution: usize) -> SceneReport {
+        let boxes: Vec<Rectangle> = self.shapes.iter().map(|s| s.bounding_box()).collect();
+
+        let mut overlaps = Vec::new();
+        for first in 0..self.shapes.len() {
+            for second in first + 1..self.shapes.len() {
+                let Some(common) = boxes[first].intersection(&boxes[second]) else {
+                    continue;
+                };
+                let (a, b) = (&self.shapes[first], &self.shapes[second]);
+                let area = estimate_area(&common, resolution, |p| a.contains(p) && b.contains(p));
+                if area > 0.0 {
+                    overlaps.push(Overlap {
+                        first,
+                        second,
+                        area,
+                    });
+                }
+            }
+        }
+
+        let covered_area = self.bounding_box().map_or(0.0, |bounds| {
+            estimate_area(&bounds, resolution, |p| {
+                self.shapes.iter().any(|shape| shape.contains(p))
+            })
+        });
+
+        SceneReport {
+            total_area: self.total_area(),
+            covered_area,
+            overlaps,
+        }
+    }
+}
+
+impl From<Vec<Box<dyn Shape>>> for Scene {
+    fn from(shapes: Vec<Box<dyn Shape>>) -> Scene {
+        Scene { shapes }
+    }
+}
+
+// Área de la parte de `bounds` en la que se cumple `inside`, contando los centros de
+// una rejilla de `resolution` × `resolution` celdas.
+fn estimate_area(bounds: &Rectangle, resolution: usize, inside: impl Fn(Point) -> bool) -> f64 {
+    let resolution = resolution.max(1);
+    let (min, width, height) = (bounds.min(), bounds.width(), bounds.height());
+    let cell = Vector2::new(width / resolution as f64, height / resolution as f64);
+
+    let mut hits = 0usize;
+    for i in 0..resolution {
+        for j in 0..resolution {
+            let point = min + Vector2::new(cell.x * (i as f64 + 0.5), cell.y * (j as f64 + 0.5));
+            hits += usize::from(inside(point));
+        }
+    }
+    width * height * hits as f64 / (resolution * resolution) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle_contains() {
+        let triangle = Triangle::new(
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 4.0),
+        );
+        assert!(triangle.contains(Point::new(1.0, 1.0)));
+        assert!(triangle.contains(Point::new(2.0, 2.0)));
+        assert!(!triangle.contains(Point::new(3.0, 3.0)));
+        assert!(!triangle.contains(Point::new(-1.0, 1.0)));
+    }
+
+    #[test]
+    fn degenerate_triangle_contains_only_its_segment() {
+        let flat = Triangle::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+        );
+        assert_eq!(flat.area(), 0.0);
+        assert!(flat.contains(Point::new(1.5, 0.0)));
+        assert!(flat.contains(Point::new(2.0, 0.0)));
+        assert!(!flat.contains(Point::new(50.0, 0.0)));
+        assert!(!flat.contains(Point::new(-0.5, 0.0)));
+        assert!(!flat.contains(Point::new(1.0, 0.5)));
+
+        let point = Triangle::new(
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 1.0),
+        );
+        assert!(point.contains(Point::new(1.0, 1.0)));
+        assert!(!point.contains(Point::new(1.0, 2.0)));
+    }
+
+    fn kind(shape: &dyn Shape) -> String {
+        format!("{shape:?}").split(' ').next().unwrap().to_owned()
+    }
+
+    #[test]
+    fn rotated_rectangle_becomes_polygon() {
+        let rectangle = Rectangle::new(Point::new(0.0, 0.0), Point::new(4.0, 2.0));
+        let rotated = rectangle.transformed(&Transform2D::rotate(PI / 4.0));
+        assert_eq!(kind(rotated.as_ref()), "Polygon");
+        assert!((rotated.area() - 8.0).abs() < 1e-9);
+        assert!((rotated.perimeter() - 12.0).abs() < 1e-9);
+
+        let scaled = rectangle.transformed(&Transform2D::scale(-1.0, 3.0));
+        assert_eq!(kind(scaled.as_ref()), "Rectangle");
+        assert!((scaled.area() - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn circle_under_non_uniform_scale_becomes_ellipse() {
+        let circle = Circle::new(Point::new(1.0, 1.0), 2.0);
+        let transform = Transform2D::scale(3.0, 0.5).then(&Transform2D::rotate(0.4));
+        let ellipse = circle.transformed(&transform);
+        assert_eq!(kind(ellipse.as_ref()), "Ellipse");
+        let expected = circle.area() * transform.determinant().abs();
+        assert!((ellipse.area() - expected).abs() < 1e-9);
+        assert!(ellipse.contains(transform.apply(Point::new(3.0, 1.0))));
+    }
+
+    #[test]
+    fn circle_under_similarity_stays_circle() {
+        let circle = Circle::new(Point::new(1.0, 0.0), 1.0);
+        let transform = Transform2D::rotate(PI / 2.0)
+            .then(&Transform2D::scale(2.0, 2.0))
+            .then(&Transform2D::translate(1.0, 1.0));
+        let image = circle.transformed(&transform);
+        assert_eq!(kind(image.as_ref()), "Circle");
+        assert!((image.area() - 4.0 * PI).abs() < 1e-9);
+        assert!(image.centroid().distance(Point::new(1.0, 3.0)) < 1e-9);
+    }
+
+    fn p(x: f64, y: f64) -> Point {
+        Point::new(x, y)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn assert_box(shape: &dyn Shape, min: Point, max: Point) {
+        let bounds = shape.bounding_box();
+        assert!(
+            bounds.min().distance(min) < 1e-9 && bounds.max().distance(max) < 1e-9,
+            "{shape:?}: {bounds:?}"
+        );
+    }
+
+    #[test]
+    fn area_and_bounding_box() {
+        let rectangle = Rectangle::new(p(3.0, 2.0), p(-1.0, 0.0));
+        assert_eq!(Shape::area(&rectangle), 8.0);
+        assert_box(&rectangle, p(-1.0, 0.0), p(3.0, 2.0));
+
+        let circle = Circle::new(p(1.0, -1.0), 2.0);
+        assert!(close(circle.area(), 4.0 * PI));
+        assert!(close(circle.perimeter(), 4.0 * PI));
+        assert_box(&circle, p(-1.0, -3.0), p(3.0, 1.0));
+
+        let ellipse = Ellipse::new(p(0.0, 0.0), 3.0, 1.0);
+        assert!(close(ellipse.area(), 3.0 * PI));
+        assert_box(&ellipse, p(-3.0, -1.0), p(3.0, 1.0));
+        // Girada 90°, los semiejes se intercambian en la caja.
+        let rotated = ellipse.with_rotation(PI / 2.0);
+        assert!(close(rotated.area(), 3.0 * PI));
+        assert_box(&rotated, p(-1.0, -3.0), p(1.0, 3.0));
+        assert!(rotated.contains(p(0.0, 2.9)) && !rotated.contains(p(2.0, 0.0)));
+        // Una elipse con los dos semiejes iguales es un círculo.
+        assert!(close(
+            Ellipse::new(p(0.0, 0.0), 2.0, 2.0).perimeter(),
+            4.0 * PI
+        ));
+
+        let triangle = Triangle::new(p(0.0, 0.0), p(4.0, 0.0), p(1.0, 3.0));
+        assert_eq!(triangle.area(), 6.0);
+        assert!(close(
+            triangle.perimeter(),
+            4.0 + 18f64.sqrt() + 10f64.sqrt()
+        ));
+        assert_box(&triangle, p(0.0, 0.0), p(4.0, 3.0));
+        assert!(triangle.centroid().distance(p(5.0 / 3.0, 1.0)) < 1e-9);
+
+        let square =
+            Polygon::new(vec![p(0.0, 0.0), p(0.0, 2.0), p(2.0, 2.0), p(2.0, 0.0)]).unwrap();
+        assert_eq!((square.area(), square.signed_area()), (4.0, -4.0));
+        assert_eq!(square.perimeter(), 8.0);
+        assert_box(&square, p(0.0, 0.0), p(2.0, 2.0));
+        assert_eq!(square.centroid(), p(1.0, 1.0));
+        assert!(Polygon::new(vec![p(0.0, 0.0), p(1.0, 1.0)]).is_none());
+    }
+
+    #[test]
+    fn concave_polygon_contains() {
+        // Una "U" de 3 × 3 con una muesca de 1 × 2 en el centro.
+        let u = Polygon::new(vec![
+            p(0.0, 0.0),
+            p(3.0, 0.0),
+            p(3.0, 3.0),
+            p(2.0, 3.0),
+            p(2.0, 1.0),
+            p(1.0, 1.0),
+            p(1.0, 3.0),
+            p(0.0, 3.0),
+        ])
+        .unwrap();
+        assert_eq!(u.area(), 7.0);
+
+        assert!(u.contains(p(0.5, 2.5)));
+        assert!(u.contains(p(2.5, 2.5)));
+        assert!(u.contains(p(1.5, 0.5)));
+        assert!(!u.contains(p(1.5, 2.0)));
+        assert!(!u.contains(p(1.5, 3.0)));
+        assert!(!u.contains(p(4.0, 1.0)));
+        // Los bordes cuentan como dentro, también los de la muesca.
+        assert!(u.contains(p(1.0, 2.0)));
+        assert!(u.contains(p(1.5, 1.0)));
+        assert!(u.contains(p(3.0, 0.0)));
+    }
+
+    #[test]
+    fn scene_overlap_of_two_circles() {
+        // Dos círculos de radio 1 con los centros a distancia 1: el área común es
+        // 2π/3 − √3/2.
+        let scene = Scene::new()
+            .with(Circle::new(p(0.0, 0.0), 1.0))
+            .with(Circle::new(p(1.0, 0.0), 1.0))
+            .with(Triangle::new(p(5.0, 5.0), p(6.0, 5.0), p(5.0, 6.0)));
+        let lens = 2.0 * PI / 3.0 - 3f64.sqrt() / 2.0;
+
+        let report = scene.report(400);
+        assert!(close(report.total_area, 2.0 * PI + 0.5));
+        assert_eq!(report.overlaps.len(), 1);
+        let overlap = report.overlaps[0];
+        assert_eq!((overlap.first, overlap.second), (0, 1));
+        assert!((overlap.area - lens).abs() < 0.01, "{}", overlap.area);
+        assert!((report.covered_area - (2.0 * PI - lens + 0.5)).abs() < 0.05);
+
+        let bounds = scene.bounding_box().unwrap();
+        assert_eq!((bounds.min(), bounds.max()), (p(-1.0, -1.0), p(6.0, 6.0)));
+        assert_eq!(Scene::new().bounding_box(), None);
+    }
+}
@@ -0,0 +1,324 @@
+// Estadísticas de FizzBuzz sin recorrer el rango.
+// =============================================================
+// Cuántos números de un rango son divisibles por `d` se calcula directamente:
+// los múltiplos de `d` en `a..=b` son `b / d - (a - 1) / d`. Con varios divisores,
+// los que son divisibles *exactamente* por un subconjunto `S` de ellos salen por
+// inclusión–exclusión sobre los subconjuntos que contienen a `S`, usando el mínimo
+// común múltiplo de cada subconjunto (3 y 5 → 15, pero 4 y 6 → 12).
+//
+// Igual se obtiene la suma de los números sin palabra (con la fórmula de la suma
+// de una progresión aritmética) y la longitud de la salida, separando el rango por
+// número de cifras. El coste depende solo del número de reglas `k` (O(k·2^k) por
+// cada una de las 20 anchuras posibles), no de la longitud del rango.
+//
+// Las sumas pueden pasar de `i128` en los pasos intermedios, así que se opera en
+// `u128` con aritmética modular (`wrapping_*`): el resultado final sí cabe.
+//
+// Como la memoria y el tiempo crecen con 2^k, hay un límite: con más de
+// `MAX_STATS_RULES` reglas, `stats` devuelve `None` (igual que si no se pudiera
+// calcular) en lugar de reservar gigabytes o desbordar el desplazamiento `1 << k`.
+
+use std::borrow::Cow;
+use std::ops::RangeBounds;
+
+use crate::number_theory::gcd;
+use crate::{classic_rules, inclusive_bounds, Fallback, FizzBuzzRules, NumberFormat};
+
+// Estadísticas de las reglas clásicas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    pub fizz: u128,
+    pub buzz: u128,
+    pub fizzbuzz: u128,
+    // Números que se escriben tal cual.
+    pub plain: u128,
+    // Suma de esos números.
+    pub plain_sum: u128,
+    // Bytes de la salida, saltos de línea incluidos.
+    pub output_len: u128,
+}
+
+// Estadísticas de un conjunto de reglas cualquiera.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RuleStats {
+    // Cuántas veces aparece cada palabra, en el orden en el que se combinan las reglas.
+    pub words: Vec<(String, u128)>,
+    // Números para los que se escribe el `Fallback`.
+    pub plain: u128,
+    // Suma de esos números.
+    pub plain_sum: u128,
+    // Bytes de la salida, saltos de línea incluidos.
+    pub output_len: u128,
+}
+
+impl RuleStats {
+    // Cuántas veces aparece `word` (`0` si no aparece nunca).
+    pub fn count(&self, word: &str) -> u128 {
+        self.words
+            .iter()
+            .find(|(other, _)| other == word)
+            .map_or(0, |&(_, count)| count)
+    }
+
+    // Líneas de la salida.
+    pub fn lines(&self) -> u128 {
+        self.words.iter().map(|&(_, count)| count).sum::<u128>() + self.plain
+    }
+}
+
+// Reglas de divisibilidad que admite `FizzBuzzRules::stats` (las de divisor `0` no
+// cuentan): 2^16 subconjuntos son unos pocos megabytes por tabla.
+pub const MAX_STATS_RULES: usize = 16;
+
+// Los mínimos comunes múltiplos mayores que `u64::MAX` se guardan como este valor:
+// en el rango solo tienen como múltiplo al `0`, igual que cualquier otro mayor.
+const TOO_BIG: u128 = 1 << 64;
+
+// Múltiplos de `d` en `a..=b`.
+fn count_multiples(d: u128, a: u64, b: u64) -> u128 {
+    let (a, b) = (u128::from(a), u128::from(b));
+    match a {
+        // El `0` es múltiplo de todos.
+        0 => b / d + 1,
+        _ => b / d - (a - 1) / d,
+    }
+}
+
+// Suma de los múltiplos de `d` en `a..=b`: `d * (lo + ... + hi)`.
+fn sum_multiples(d: u128, a: u64, b: u64) -> u128 {
+    let (lo, hi) = (u128::from(a).div_ceil(d), u128::from(b) / d);
+    if lo > hi {
+        return 0;
+    }
+
+    // `(lo + hi) * (hi - lo + 1) / 2`, dividiendo antes el factor par para no desbordar.
+    let (first, second) = (lo + hi, hi - lo + 1);
+    let sum = if first % 2 == 0 {
+        (first / 2).wrapping_mul(second)
+    } else {
+        first.wrapping_mul(second / 2)
+    };
+    sum.wrapping_mul(d)
+}
+
+// A partir de los valores de cada subconjunto (p.ej. los múltiplos de su mínimo
+// común múltiplo), deja en cada posición la parte que corresponde *solo* a ese
+// subconjunto: inclusión–exclusión sobre los subconjuntos que lo contienen.
+fn exclusive(values: &mut [u128], k: usize) {
+    for i in 0..k {
+        let bit = 1 << i;
+        for mask in 0..values.len() {
+            if mask & bit == 0 {
+                values[mask] = values[mask].wrapping_sub(values[mask | bit]);
+            }
+        }
+    }
+}
+
+// Los números de `a..=b` agrupados por número de cifras: (primero, último, cifras).
+fn digit_widths(a: u64, b: u64) -> impl Iterator<Item = (u64, u64, u128)> {
+    (1..=20).filter_map(move |width: u32| {
+        let first = if width == 1 { 0 } else { 10u64.pow(width - 1) };
+        let last = 10u64.checked_pow(width).map_or(u64::MAX, |power| power - 1);
+        let (first, last) = (first.max(a), last.min(b));
+        (first <= last).then_some((first, last, u128::from(width)))
+    })
+}
+
+impl FizzBuzzRules {
+    // Las estadísticas de `range` calculadas sin recorrerlo. Solo es posible si todas
+    // las reglas son de divisibilidad, no hay más de `MAX_STATS_RULES` y los números
+    // se escriben en decimal: si no, devuelve `None`.
+    pub fn stats<R: RangeBounds<u64>>(&self, range: R) -> Option<RuleStats> {
+        if self.number_format() != NumberFormat::Decimal && *self.fallback() == Fallback::Number {
+            return None;
+        }
+
+        // Las reglas con divisor `0` nunca coinciden: se pueden ignorar.
+        let mut divisors = Vec::new();
+        for rule in self.rules() {
+            match rule.divisor_word()? {
+                (0, _) => {}
+                (divisor, word) => divisors.push((rule.mode(), divisor, word)),
+            }
+        }
+        if divisors.len() > MAX_STATS_RULES {
+            return None;
+        }
+
+        let (a, b) = match inclusive_bounds(&range) {
+            Some(bounds) => bounds,
+            None => return Some(RuleStats::default()),
+        };
+
+        // Mínimo común múltiplo de cada subconjunto de reglas (el bit `i` es la regla `i`).
+        let k = divisors.len();
+        let mut lcms = vec![1u128; 1 << k];
+        for mask in 1..lcms.len() {
+            let i = mask.trailing_zeros() as usize;
+            let (rest, divisor) = (lcms[mask & (mask - 1)], u128::from(divisors[i].1));
+            lcms[mask] = (rest / gcd(rest, divisor) * divisor).min(TOO_BIG);
+        }
+
+        // La palabra de cada subconjunto, o `None` si se escribe el `Fallback`.
+        let words: Vec<Option<Cow<str>>> = (0..lcms.len())
+            .map(|mask| {
+                self.combine(
+                    divisors
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, _)| mask & (1 << i) != 0)
+                        .map(|(_, &(mode, _, word))| (mode, Cow::Borrowed(word))),
+                )
+            })
+            .collect();
+
+        // Cuántos números hay de cada subconjunto y cuántos bytes ocupan sus líneas,
+        // separando por número de cifras para saber cuánto ocupa el `Fallback::Number`.
+        let mut counts = vec![0u128; lcms.len()];
+        let mut stats = RuleStats::default();
+        for (first, last, width) in digit_widths(a, b) {
+            let mut exact: Vec<u128> = lcms
+                .iter()
+                .map(|&lcm| count_multiples(lcm, first, last))
+                .collect();
+            exclusive(&mut exact, k);
+
+            for (mask, &count) in exact.iter().enumerate() {
+                counts[mask] += count;
+                let len = match (&words[mask], self.fallback()) {
+                    (Some(word), _) => word.len() as u128,
+                    (None, Fallback::Number) => width,
+                    (None, Fallback::Text(text)) => text.len() as u128,
+                };
+                stats.output_len += count * (len + 1);
+            }
+        }
+
+        let mut sums: Vec<u128> = lcms.iter().map(|&lcm| sum_multiples(lcm, a, b)).collect();
+        exclusive(&mut sums, k);
+
+        for (mask, word) in words.into_iter().enumerate() {
+            match word {
+                None => {
+                    stats.plain += counts[mask];
+                    stats.plain_sum = stats.plain_sum.wrapping_add(sums[mask]);
+                }
+                Some(_) if counts[mask] == 0 => {}
+                Some(word) => match stats.words.iter_mut().find(|(other, _)| *other == word) {
+                    Some((_, count)) => *count += counts[mask],
+                    None => stats.words.push((word.into_owned(), counts[mask])),
+                },
+            }
+        }
+
+        Some(stats)
+    }
+}
+
+// Las estadísticas de `fizzbuzz` sobre `range` (sin inicio se empieza en `1`),
+// p.ej. `fizzbuzz_stats(1..=100)` tiene 27 "fizz", 14 "buzz" y 6 "fizzbuzz".
+pub fn fizzbuzz_stats<R: RangeBounds<u64>>(range: R) -> Stats {
+    let stats = classic_rules()
+        .stats(range)
+        .expect("las reglas clásicas son de divisibilidad");
+
+    Stats {
+        fizz: stats.count("fizz"),
+        buzz: stats.count("buzz"),
+        fizzbuzz: stats.count("fizzbuzz"),
+        plain: stats.plain,
+        plain_sum: stats.plain_sum,
+        output_len: stats.output_len,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Rule, RuleMode};
+
+    // Las mismas estadísticas, recorriendo el rango número a número.
+    fn brute_force(rules: &FizzBuzzRules, a: u64, b: u64) -> RuleStats {
+        let mut stats = RuleStats::default();
+        for n in a..=b {
+            stats.output_len += rules.token(n).len() as u128 + 1;
+            match rules.word(n) {
+                None => {
+                    stats.plain += 1;
+                    stats.plain_sum += u128::from(n);
+                }
+                Some(word) => match stats.words.iter_mut().find(|(other, _)| *other == word) {
+                    Some((_, count)) => *count += 1,
+                    None => stats.words.push((word.into_owned(), 1)),
+                },
+            }
+        }
+        stats
+    }
+
+    fn assert_matches(rules: &FizzBuzzRules, a: u64, b: u64) {
+        let (mut fast, mut slow) = (rules.stats(a..=b).unwrap(), brute_force(rules, a, b));
+        // El orden de las palabras puede variar: se comparan ordenadas.
+        fast.words.sort();
+        slow.words.sort();
+        assert_eq!(fast, slow, "{a}..={b}");
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let rule_sets = [
+            FizzBuzzRules::classic(),
+            FizzBuzzRules::new(),
+            FizzBuzzRules::new()
+                .rule(4, "four")
+                .rule(6, "six")
+                .rule(0, "never"),
+            FizzBuzzRules::classic().rule(7, "bazz").with_separator("-"),
+            FizzBuzzRules::classic()
+                .with(Rule::divisible_by(15, "both").with_mode(RuleMode::Override))
+                .with_fallback(Fallback::Text("-".to_owned())),
+            FizzBuzzRules::new().rule(2, "even").rule(2, "again"),
+        ];
+        for rules in &rule_sets {
+            for (a, b) in [(1, 100), (0, 30), (95, 1_005), (9_990, 100_010), (7, 7)] {
+                assert_matches(rules, a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn classic_totals() {
+        let stats = fizzbuzz_stats(1..=100);
+        assert_eq!((stats.fizz, stats.buzz, stats.fizzbuzz), (27, 14, 6));
+        assert_eq!(stats.plain, 53);
+        assert_eq!(stats.output_len, crate::fizzbuzz_output_len(100));
+    }
+
+    #[test]
+    fn top_of_u64_range() {
+        let rules = FizzBuzzRules::classic().rule(7, "bazz");
+        assert_matches(&rules, u64::MAX - 2_000, u64::MAX);
+    }
+
+    #[test]
+    fn too_many_rules() {
+        let mut rules = FizzBuzzRules::new();
+        for divisor in 2..2 + MAX_STATS_RULES as u64 {
+            rules.push(divisor, "x");
+        }
+        assert!(rules.stats(1..=100).is_some());
+
+        // Las de divisor `0` no cuentan para el límite.
+        rules.push(0, "zero");
+        assert!(rules.stats(1..=100).is_some());
+
+        rules.push(1_000, "y");
+        assert_eq!(rules.stats(1..=100), None);
+
+        for divisor in 0..64 {
+            rules.push(divisor + 2_000, "z");
+        }
+        assert_eq!(rules.stats(1..=100), None);
+    }
+}
@@ -0,0 +1,221 @@
+// Generador de FizzBuzz de alto rendimiento.
+// =============================================================
+// `fizzbuzz_to` llama a `println!` una vez por número: bloquea `stdout`, formatea
+// el número y vacía la línea cada vez. Aquí, en cambio:
+// • la salida se repite con periodo 15, así que se recorre en bloques de 15 con una
+//   tabla fija que dice qué posiciones son palabras y cuáles números;
+// • cada bloque de 300 números (20 periodos) se guarda ya escrito como plantilla,
+//   y para pasar al siguiente se suma 3 "a mano" (con acarreo) a las centenas de
+//   sus números en ASCII, sin volver a formatearlos;
+// • todo se acumula en un búfer grande que se escribe de una sola vez.
+// La salida es idéntica, byte a byte, a la de `fizzbuzz_to`.
+
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+// Tamaño del búfer de salida antes de volcarlo en el `Write`.
+const CHUNK_SIZE: usize = 1 << 20;
+
+// Qué imprimir según `n % 15`: una palabra o, con `None`, el propio número.
+const PATTERN: [Option<&[u8]>; 15] = [
+    Some(b"fizzbuzz"),
+    None,
+    None,
+    Some(b"fizz"),
+    None,
+    Some(b"buzz"),
+    Some(b"fizz"),
+    None,
+    None,
+    Some(b"fizz"),
+    Some(b"buzz"),
+    None,
+    Some(b"fizz"),
+    None,
+    None,
+];
+
+// Escribe una línea suelta formateando el número (solo para los bloques incompletos
+// del principio y del final del rango).
+fn push_line(buffer: &mut Vec<u8>, n: u64) {
+    match PATTERN[(n % 15) as usize] {
+        Some(word) => buffer.extend_from_slice(word),
+        None => buffer.extend_from_slice(n.to_string().as_bytes()),
+    }
+    buffer.push(b'\n');
+}
+
+// Suma `amount` (un solo dígito) a un número escrito en dígitos ASCII, propagando
+// el acarreo. Devuelve `false` si el resultado necesita un dígito más (y entonces
+// el contenido de `digits` ya no es válido).
+fn add_in_place(digits: &mut [u8], amount: u8) -> bool {
+    let mut carry = amount;
+    for digit in digits.iter_mut().rev() {
+        *digit += carry;
+        if *digit <= b'9' {
+            return true;
+        }
+        *digit -= 10;
+        carry = 1;
+    }
+    false
+}
+
+// Números que cubre cada plantilla: 300 es múltiplo de 15 (la salida se repite) y
+// de 100 (las decenas y unidades no cambian), así que avanzar una plantilla solo
+// toca las centenas y lo que haya por encima.
+const BLOCK_LEN: u64 = 300;
+
+// La salida de un bloque completo de `BLOCK_LEN` números, `first..=first + BLOCK_LEN - 1`
+// con `first % 15 == 1`, junto con la posición de cada uno de sus números.
+// El último bloque posible termina justo en `u64::MAX` (2⁶⁴ − 300 ≡ 1 mód 15), así que
+// el rango es cerrado: `first + BLOCK_LEN` desbordaría.
+struct Block {
+    bytes: Vec<u8>,
+    numbers: Vec<(usize, usize)>,
+}
+
+impl Block {
+    fn new(first: u64) -> Block {
+        let mut block = Block {
+            bytes: Vec::new(),
+            numbers: Vec::new(),
+        };
+
+        for n in first..=first + (BLOCK_LEN - 1) {
+            let begin = block.bytes.len();
+            push_line(&mut block.bytes, n);
+            if PATTERN[(n % 15) as usize].is_none() {
+                block.numbers.push((begin, block.bytes.len() - 1));
+            }
+        }
+        block
+    }
+
+    // Pasa al bloque siguiente sumando `BLOCK_LEN` a cada número en su sitio: 3 a
+    // todo salvo las dos últimas cifras. Devuelve `false` si algún número gana un
+    // dígito (o tiene menos de tres): entonces hay que reconstruir el bloque.
+    fn advance(&mut self) -> bool {
+        let Block { bytes, numbers } = self;
+        numbers
+            .iter()
+            .all(|&(begin, end)| end - begin > 2 && add_in_place(&mut bytes[begin..end - 2], 3))
+    }
+}
+
+// Escribe las líneas de `range` (igual que `fizzbuzz_to`, pero para cualquier tramo).
+pub fn write_fizzbuzz_range_fast<W: Write>(
+    out: &mut W,
+    range: RangeInclusive<u64>,
+) -> io::Result<()> {
+    let (start, end) = range.into_inner();
+    if start > end {
+        return Ok(());
+    }
+
+    // Trabajamos con un rango semiabierto en `u128` para que `u64::MAX` no desborde.
+    let end = u128::from(end) + 1;
+    let mut n = u128::from(start);
+    // Cabe un trozo completo más una plantilla (21 bytes por línea como mucho).
+    let mut buffer = Vec::with_capacity(CHUNK_SIZE + BLOCK_LEN as usize * 21);
+
+    // Bloque incompleto inicial, hasta el primer `n % 15 == 1`.
+    while n < end && n % 15 != 1 {
+        push_line(&mut buffer, n as u64);
+        n += 1;
+    }
+
+    // Bloques completos: se copia la plantilla y se le suma `BLOCK_LEN` a sus números.
+    let block_len = u128::from(BLOCK_LEN);
+    if end - n >= block_len {
+        let mut block = Block::new(n as u64);
+        loop {
+            buffer.extend_from_slice(&block.bytes);
+            n += block_len;
+
+            if buffer.len() >= CHUNK_SIZE {
+                out.write_all(&buffer)?;
+                buffer.clear();
+            }
+
+            if end - n < block_len {
+                break;
+            }
+            if !block.advance() {
+                block = Block::new(n as u64);
+            }
+        }
+    }
+
+    // Bloque incompleto final.
+    while n < end {
+        push_line(&mut buffer, n as u64);
+        n += 1;
+    }
+
+    out.write_all(&buffer)?;
+    out.flush()
+}
+
+// Equivalente rápido de `fizzbuzz_to(n)` sobre cualquier `Write`.
+pub fn write_fizzbuzz_fast<W: Write>(out: &mut W, n: u64) -> io::Result<()> {
+    write_fizzbuzz_range_fast(out, 1..=n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{write_fizzbuzz_to, FizzBuzzRules};
+
+    fn expected(range: RangeInclusive<u64>) -> Vec<u8> {
+        let rules = FizzBuzzRules::classic();
+        let mut out = Vec::new();
+        for n in range {
+            rules.write_fizzbuzz(&mut out, n).unwrap();
+        }
+        out
+    }
+
+    fn fast(range: RangeInclusive<u64>) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_fizzbuzz_range_fast(&mut out, range).unwrap();
+        out
+    }
+
+    #[test]
+    fn matches_write_fizzbuzz_to() {
+        for n in [0, 1, 14, 15, 16, 299, 300, 301, 1000, 12_345] {
+            let mut slow = Vec::new();
+            write_fizzbuzz_to(&mut slow, n as u32).unwrap();
+            let mut quick = Vec::new();
+            write_fizzbuzz_fast(&mut quick, n).unwrap();
+            assert_eq!(quick, slow, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn crosses_digit_boundaries() {
+        // Los números ganan un dígito a mitad de bloque: hay que reconstruirlo.
+        for (start, end) in [(1, 2_000), (9_500, 10_700), (999_000, 1_001_000)] {
+            assert_eq!(fast(start..=end), expected(start..=end), "{start}..={end}");
+        }
+    }
+
+    #[test]
+    fn top_of_u64_range() {
+        // Un bloque completo que acaba justo en `u64::MAX`.
+        let first = u64::MAX - (BLOCK_LEN - 1);
+        assert_eq!(first % 15, 1);
+        assert_eq!(fast(first..=u64::MAX), expected(first..=u64::MAX));
+
+        let start = u64::MAX - 1_000;
+        assert_eq!(fast(start..=u64::MAX), expected(start..=u64::MAX));
+        assert_eq!(fast(u64::MAX..=u64::MAX), expected(u64::MAX..=u64::MAX));
+    }
+
+    #[test]
+    fn empty_range() {
+        let (start, end) = (10, 9);
+        assert!(fast(start..=end).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+// FizzBuzz calculado al compilar.
+// =============================================================
+// Para incrustar FizzBuzz en un binario (p.ej. en un firmware) sin gastar nada al
+// ejecutarlo, todo lo de este módulo es `const fn`:
+// • `fizzbuzz_table::<N>()` es la tabla de `Token`s de 1 a `N`;
+// • `fizzbuzz_static!(n)` es la salida de `fizzbuzz_to(n)` como `&'static str`,
+//   construida byte a byte por `fizzbuzz_bytes`.
+//
+// La evaluación en tiempo de compilación es mucho más lenta que el código normal
+// (y el compilador avisa si tarda demasiado), así que está pensado para `N` pequeños:
+// unos pocos miles de líneas.
+
+use crate::Token;
+
+// Los tokens de `1..=N`: `fizzbuzz_table::<15>()[14]` es `Token::FizzBuzz`.
+pub const fn fizzbuzz_table<const N: usize>() -> [Token; N] {
+    let mut table = [Token::Number(0); N];
+    // Sin `for`, que no se puede usar en una `const fn`.
+    let mut i = 0;
+    while i < N {
+        table[i] = Token::of(i as u64 + 1);
+        i += 1;
+    }
+    table
+}
+
+// Copia `bytes` en `out` a partir de `pos` y devuelve la posición siguiente.
+const fn push_bytes(out: &mut [u8], mut pos: usize, bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        out[pos] = bytes[i];
+        pos += 1;
+        i += 1;
+    }
+    pos
+}
+
+// Escribe `n` en decimal, de la última cifra a la primera.
+const fn push_number(out: &mut [u8], pos: usize, n: u64) -> usize {
+    let mut width = 1;
+    let mut rest = n / 10;
+    while rest > 0 {
+        width += 1;
+        rest /= 10;
+    }
+
+    let mut rest = n;
+    let mut i = width;
+    while i > 0 {
+        i -= 1;
+        out[pos + i] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+    }
+    pos + width
+}
+
+// La salida de `fizzbuzz_to(n)` como bytes. `LEN` tiene que ser exactamente
+// `fizzbuzz_output_len(n)`: es lo que hace `fizzbuzz_static!`, ya que el tamaño de un
+// array no puede depender todavía de un parámetro de la función.
+pub const fn fizzbuzz_bytes<const LEN: usize>(n: u64) -> [u8; LEN] {
+    let mut out = [0; LEN];
+    let mut pos = 0;
+    let mut i = 1;
+    while i <= n {
+        pos = match Token::of(i) {
+            Token::Fizz => push_bytes(&mut out, pos, b"fizz"),
+            Token::Buzz => push_bytes(&mut out, pos, b"buzz"),
+            Token::FizzBuzz => push_bytes(&mut out, pos, b"fizzbuzz"),
+            Token::Number(n) => push_number(&mut out, pos, n),
+        };
+        out[pos] = b'\n';
+        pos += 1;
+        i += 1;
+    }
+    assert!(pos == LEN, "`LEN` no coincide con `fizzbuzz_output_len(n)`");
+    out
+}
+
+// `fizzbuzz_static!(15)`: la salida de `fizzbuzz_to(15)` como `&'static str`,
+// calculada al compilar.
+#[macro_export]
+macro_rules! fizzbuzz_static {
+    ($n:expr) => {{
+        const N: u64 = $n;
+        const BYTES: [u8; $crate::fizzbuzz_output_len(N) as usize] = $crate::fizzbuzz_bytes(N);
+        const OUTPUT: &str = match ::core::str::from_utf8(&BYTES) {
+            Ok(output) => output,
+            Err(_) => panic!("la salida de FizzBuzz es ASCII"),
+        };
+        OUTPUT
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::write_fizzbuzz_to;
+
+    fn runtime(n: u32) -> String {
+        let mut out = Vec::new();
+        write_fizzbuzz_to(&mut out, n).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn static_output_matches_runtime() {
+        assert_eq!(crate::fizzbuzz_static!(0), runtime(0));
+        assert_eq!(crate::fizzbuzz_static!(1), runtime(1));
+        assert_eq!(crate::fizzbuzz_static!(15), runtime(15));
+        assert_eq!(crate::fizzbuzz_static!(100), runtime(100));
+        assert_eq!(crate::fizzbuzz_static!(1000), runtime(1000));
+    }
+
+    #[test]
+    fn table_is_built_at_compile_time() {
+        const TABLE: [Token; 15] = fizzbuzz_table::<15>();
+        const LAST_IS_FIZZBUZZ: bool = matches!(TABLE[14], Token::FizzBuzz);
+        const { assert!(LAST_IS_FIZZBUZZ) };
+
+        assert_eq!(TABLE[0], Token::Number(1));
+        assert_eq!(TABLE[2], Token::Fizz);
+        assert_eq!(TABLE[4], Token::Buzz);
+        assert!(fizzbuzz_table::<0>().is_empty());
+    }
+}
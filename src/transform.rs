@@ -0,0 +1,245 @@
+// Transformaciones afines.
+// =============================================================
+// Una transformación afín del plano (giro, escalado, cizalla, traslación y sus
+// combinaciones) es una matriz de 3 × 3 que actúa sobre coordenadas homogéneas
+// `(x, y, 1)`:
+//
+//     | a  b  tx |   | x |   | a·x + b·y + tx |
+//     | c  d  ty | · | y | = | c·x + d·y + ty |
+//     | 0  0  1  |   | 1 |   |       1        |
+//
+// Así, combinar transformaciones es multiplicar matrices: `t2 * t1` aplica primero
+// `t1` y después `t2` (igual que en la notación matemática); `t1.then(&t2)` es lo
+// mismo, pero se lee en el orden en que se aplican.
+//
+// Los vectores (`Vector2`) son desplazamientos: les afecta todo salvo la traslación.
+
+use std::ops::Mul;
+
+use crate::{Point, Vector2};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform2D {
+    // Por filas. La última fila es siempre `[0, 0, 1]`.
+    matrix: [[f64; 3]; 3],
+}
+
+impl Transform2D {
+    fn from_rows(a: f64, b: f64, tx: f64, c: f64, d: f64, ty: f64) -> Transform2D {
+        Transform2D {
+            matrix: [[a, b, tx], [c, d, ty], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    pub fn identity() -> Transform2D {
+        Transform2D::from_rows(1.0, 0.0, 0.0, 0.0, 1.0, 0.0)
+    }
+
+    pub fn translate(x: f64, y: f64) -> Transform2D {
+        Transform2D::from_rows(1.0, 0.0, x, 0.0, 1.0, y)
+    }
+
+    // Giro de `angle` radianes en sentido antihorario alrededor del origen.
+    pub fn rotate(angle: f64) -> Transform2D {
+        let (sin, cos) = angle.sin_cos();
+        Transform2D::from_rows(cos, -sin, 0.0, sin, cos, 0.0)
+    }
+
+    // Giro alrededor de `center`: se lleva `center` al origen, se gira y se devuelve.
+    pub fn rotate_around(center: Point, angle: f64) -> Transform2D {
+        Transform2D::translate(-center.x, -center.y)
+            .then(&Transform2D::rotate(angle))
+            .then(&Transform2D::translate(center.x, center.y))
+    }
+
+    // Escalado respecto al origen (un factor negativo refleja).
+    pub fn scale(x: f64, y: f64) -> Transform2D {
+        Transform2D::from_rows(x, 0.0, 0.0, 0.0, y, 0.0)
+    }
+
+    // Cizalla: `x' = x + kx·y`, `y' = y + ky·x`.
+    pub fn shear(kx: f64, ky: f64) -> Transform2D {
+        Transform2D::from_rows(1.0, kx, 0.0, ky, 1.0, 0.0)
+    }
+
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    // Aplica `self` y después `next`.
+    pub fn then(&self, next: &Transform2D) -> Transform2D {
+        *next * *self
+    }
+
+    // Determinante de la parte lineal: el factor por el que se multiplican las áreas
+    // (negativo si la transformación refleja).
+    pub fn determinant(&self) -> f64 {
+        let [[a, b, _], [c, d, _], _] = self.matrix;
+        a * d - b * c
+    }
+
+    // La transformación que deshace `self`, o `None` si no la hay (determinante `0`:
+    // aplasta el plano en una recta o un punto).
+    pub fn inverse(&self) -> Option<Transform2D> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        // Inversa de la parte lineal (adjunta / determinante) y traslación deshecha.
+        let [[a, b, tx], [c, d, ty], _] = self.matrix;
+        let (ia, ib, ic, id) = (d / det, -b / det, -c / det, a / det);
+        Some(Transform2D::from_rows(
+            ia,
+            ib,
+            -(ia * tx + ib * ty),
+            ic,
+            id,
+            -(ic * tx + id * ty),
+        ))
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        let [[a, b, tx], [c, d, ty], _] = self.matrix;
+        Point::new(
+            a * point.x + b * point.y + tx,
+            c * point.x + d * point.y + ty,
+        )
+    }
+
+    pub fn apply_vector(&self, vector: Vector2) -> Vector2 {
+        let [[a, b, _], [c, d, _], _] = self.matrix;
+        Vector2::new(a * vector.x + b * vector.y, c * vector.x + d * vector.y)
+    }
+
+    // ¿Conserva los ejes? (sin giro ni cizalla: solo escalado, reflexión y traslación).
+    pub fn is_axis_aligned(&self) -> bool {
+        let [[_, b, _], [c, _, _], _] = self.matrix;
+        b == 0.0 && c == 0.0
+    }
+
+    // Si es una semejanza (conserva los ángulos: giro, escalado uniforme, reflexión y
+    // traslación), el factor de escala; si no, `None`.
+    pub fn similarity_scale(&self) -> Option<f64> {
+        let (x, y) = (
+            self.apply_vector(Vector2::new(1.0, 0.0)),
+            self.apply_vector(Vector2::new(0.0, 1.0)),
+        );
+        let tolerance = 1e-12 * (x.length_squared() + y.length_squared()).max(1.0);
+        let similar = x.dot(y).abs() <= tolerance
+            && (x.length_squared() - y.length_squared()).abs() <= tolerance;
+        similar.then(|| x.length())
+    }
+
+    // Descomposición en valores singulares de la parte lineal: la transformación
+    // convierte la circunferencia unidad en una elipse de semiejes `(σ1, σ2)` cuyo
+    // primer eje forma el ángulo devuelto con el eje `x`.
+    pub(crate) fn singular_values(&self) -> (f64, f64, f64) {
+        let [[a, b, _], [c, d, _], _] = self.matrix;
+        let (e, f, g, h) = ((a + d) / 2.0, (a - d) / 2.0, (c + b) / 2.0, (c - b) / 2.0);
+        let (q, r) = (e.hypot(h), f.hypot(g));
+        let angle = (g.atan2(f) + h.atan2(e)) / 2.0;
+        (q + r, (q - r).abs(), angle)
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Transform2D {
+        Transform2D::identity()
+    }
+}
+
+// Producto de matrices: `self * other` aplica primero `other`.
+impl Mul for Transform2D {
+    type Output = Transform2D;
+
+    fn mul(self, other: Transform2D) -> Transform2D {
+        let mut matrix = [[0.0; 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.matrix[i][k] * other.matrix[k][j]).sum();
+            }
+        }
+        Transform2D { matrix }
+    }
+}
+
+impl Mul<Point> for Transform2D {
+    type Output = Point;
+
+    fn mul(self, point: Point) -> Point {
+        self.apply(point)
+    }
+}
+
+impl Mul<Vector2> for Transform2D {
+    type Output = Vector2;
+
+    fn mul(self, vector: Vector2) -> Vector2 {
+        self.apply_vector(vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    fn assert_close(a: Point, b: Point) {
+        assert!(a.distance(b) < 1e-9, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn then_applies_self_first() {
+        let (a, b) = (
+            Transform2D::translate(1.0, 0.0),
+            Transform2D::rotate(FRAC_PI_2),
+        );
+        let p = Point::new(1.0, 0.0);
+
+        // Trasladar (2, 0) y girar → (0, 2); girar (0, 1) y trasladar → (1, 1).
+        assert_close(a.then(&b).apply(p), Point::new(0.0, 2.0));
+        assert_close(b.then(&a).apply(p), Point::new(1.0, 1.0));
+        assert_eq!(a.then(&b), b * a);
+        assert_close(b * (a * p), a.then(&b) * p);
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        let t = Transform2D::scale(2.0, -3.0)
+            .then(&Transform2D::shear(0.5, 0.0))
+            .then(&Transform2D::rotate_around(Point::new(1.0, 2.0), 0.7))
+            .then(&Transform2D::translate(-4.0, 5.0));
+        let inverse = t.inverse().unwrap();
+        let p = Point::new(3.5, -1.25);
+        assert_close(inverse.apply(t.apply(p)), p);
+        assert_close(t.apply(inverse.apply(p)), p);
+        assert!((t.determinant() * inverse.determinant() - 1.0).abs() < 1e-12);
+
+        // Los vectores no se trasladan.
+        let v = Transform2D::translate(7.0, 7.0).apply_vector(Vector2::new(1.0, 2.0));
+        assert_eq!(v, Vector2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        assert_eq!(Transform2D::scale(2.0, 0.0).inverse(), None);
+        assert_eq!(Transform2D::shear(1.0, 1.0).inverse(), None);
+        assert_eq!(
+            Transform2D::identity().inverse(),
+            Some(Transform2D::identity())
+        );
+    }
+
+    #[test]
+    fn similarity_scale_and_singular_values() {
+        let similar = Transform2D::rotate(0.3).then(&Transform2D::scale(-2.0, 2.0));
+        assert!((similar.similarity_scale().unwrap() - 2.0).abs() < 1e-12);
+        assert_eq!(Transform2D::scale(2.0, 1.0).similarity_scale(), None);
+
+        let (s1, s2, _) = Transform2D::scale(3.0, 2.0)
+            .then(&Transform2D::rotate(1.0))
+            .singular_values();
+        assert!((s1 - 3.0).abs() < 1e-12 && (s2 - 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,355 @@
+// Verificador de salidas de FizzBuzz.
+// =============================================================
+// La operación inversa de `fizzbuzz_to`: lee una salida cualquiera (un `BufRead`)
+// y comprueba, línea a línea, que la línea `n` es el token que las reglas esperan
+// para `n`. Al encontrar el primer error se detiene y lo describe: número de línea,
+// valor esperado frente al encontrado y unas cuantas líneas de contexto alrededor.
+//
+// Los finales de línea se comprueban según el nivel de exigencia (`Strictness`).
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::FizzBuzzRules;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Strictness {
+    // Byte a byte como `fizzbuzz_to`: solo `\n`, también al final de la última línea.
+    Strict,
+    // Como `Strict`, pero la última línea puede no terminar en `\n`.
+    #[default]
+    Normal,
+    // Como `Normal`, y además se aceptan finales de línea `\r\n`.
+    Lenient,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStrictness(pub String);
+
+impl fmt::Display for UnknownStrictness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&crate::message!("error.unknown_strictness", self.0))
+    }
+}
+
+impl Error for UnknownStrictness {}
+
+impl FromStr for Strictness {
+    type Err = UnknownStrictness;
+
+    fn from_str(s: &str) -> Result<Strictness, UnknownStrictness> {
+        match s {
+            "strict" => Ok(Strictness::Strict),
+            "normal" => Ok(Strictness::Normal),
+            "lenient" => Ok(Strictness::Lenient),
+            other => Err(UnknownStrictness(other.to_owned())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyOptions {
+    // Número de líneas que debe tener la entrada (`fizzbuzz_to(n)`). Con `None`
+    // se acepta cualquier longitud.
+    pub n: Option<u64>,
+    pub strictness: Strictness,
+    // Líneas de contexto que se muestran antes y después del error.
+    pub context: usize,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> VerifyOptions {
+        VerifyOptions {
+            n: None,
+            strictness: Strictness::Normal,
+            context: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MismatchKind {
+    // La línea no contiene el token esperado.
+    WrongToken,
+    // La entrada termina antes de la línea `n`.
+    MissingLine,
+    // La entrada sigue después de la línea `n`.
+    ExtraLine,
+    // La línea termina en `\r\n` y no se admite.
+    CarriageReturn,
+    // La última línea no termina en `\n` y no se admite.
+    MissingFinalNewline,
+    // La línea no es UTF-8 válido.
+    InvalidUtf8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextLine {
+    pub line: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub kind: MismatchKind,
+    pub line: u64,
+    // `None` si la línea sobra.
+    pub expected: Option<String>,
+    // `None` si la línea falta.
+    pub actual: Option<String>,
+    // Las líneas de alrededor, incluida la del error, en orden.
+    pub context: Vec<ContextLine>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let expected = self.expected.as_deref().unwrap_or_default();
+        let actual = self.actual.as_deref().unwrap_or_default();
+
+        let expected = format!("{:?}", expected);
+        let actual = format!("{:?}", actual);
+        let message = match self.kind {
+            MismatchKind::WrongToken => {
+                crate::message!("verify.wrong_token", self.line, expected, actual)
+            }
+            MismatchKind::MissingLine => {
+                crate::message!("verify.missing_line", self.line, expected)
+            }
+            MismatchKind::ExtraLine => crate::message!("verify.extra_line", self.line, actual),
+            MismatchKind::CarriageReturn => crate::message!("verify.carriage_return", self.line),
+            MismatchKind::MissingFinalNewline => {
+                crate::message!("verify.missing_final_newline", self.line)
+            }
+            MismatchKind::InvalidUtf8 => crate::message!("verify.invalid_utf8", self.line),
+        };
+        f.write_str(&message)?;
+
+        let width = self
+            .context
+            .last()
+            .map_or(1, |context| context.line.to_string().len());
+        for context in &self.context {
+            let marker = if context.line == self.line { '>' } else { ' ' };
+            write!(
+                f,
+                "\n{} {:>width$} | {}",
+                marker,
+                context.line,
+                context.text,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    // Líneas leídas y comprobadas (incluida la del error, si lo hay).
+    pub lines: u64,
+    pub mismatch: Option<Mismatch>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.mismatch.is_none()
+    }
+}
+
+// Quita el final de línea y devuelve (texto, tenía `\n`, tenía `\r\n`).
+fn split_line_ending(raw: &[u8]) -> (&[u8], bool, bool) {
+    match raw.strip_suffix(b"\n") {
+        Some(line) => match line.strip_suffix(b"\r") {
+            Some(line) => (line, true, true),
+            None => (line, true, false),
+        },
+        None => (raw, false, false),
+    }
+}
+
+// Comprueba `input` contra `rules` y devuelve el primer error, si lo hay.
+pub fn verify<R: BufRead>(
+    mut input: R,
+    rules: &FizzBuzzRules,
+    options: &VerifyOptions,
+) -> io::Result<VerifyReport> {
+    let mut before: VecDeque<ContextLine> = VecDeque::with_capacity(options.context + 1);
+    let mut raw = Vec::new();
+    let mut line = 0;
+
+    let mismatch = loop {
+        raw.clear();
+        if input.read_until(b'\n', &mut raw)? == 0 {
+            // Fin de la entrada: ¿faltan líneas?
+            match options.n {
+                Some(n) if line < n => {
+                    break Some(Mismatch {
+                        kind: MismatchKind::MissingLine,
+                        line: line + 1,
+                        expected: Some(rules.token(line + 1).into_owned()),
+                        actual: None,
+                        context: before.into(),
+                    });
+                }
+                _ => break None,
+            }
+        }
+        line += 1;
+
+        let (text, newline, crlf) = split_line_ending(&raw);
+        let text = std::str::from_utf8(text);
+        let expected = rules.token(line);
+
+        let kind = match text {
+            Err(_) => Some(MismatchKind::InvalidUtf8),
+            Ok(_) if options.n.is_some_and(|n| line > n) => Some(MismatchKind::ExtraLine),
+            Ok(text) if text != expected => Some(MismatchKind::WrongToken),
+            Ok(_) if crlf && options.strictness != Strictness::Lenient => {
+                Some(MismatchKind::CarriageReturn)
+            }
+            Ok(_) if !newline && options.strictness == Strictness::Strict => {
+                Some(MismatchKind::MissingFinalNewline)
+            }
+            Ok(_) => None,
+        };
+
+        let text = String::from_utf8_lossy(split_line_ending(&raw).0).into_owned();
+        match kind {
+            None => {
+                if before.len() == options.context {
+                    before.pop_front();
+                }
+                if options.context > 0 {
+                    before.push_back(ContextLine { line, text });
+                }
+            }
+            Some(kind) => {
+                let mut context: Vec<ContextLine> = before.into();
+                context.push(ContextLine {
+                    line,
+                    text: text.clone(),
+                });
+
+                // Contexto posterior: las siguientes líneas, tal cual.
+                for after in line + 1..=line + options.context as u64 {
+                    raw.clear();
+                    if input.read_until(b'\n', &mut raw)? == 0 {
+                        break;
+                    }
+                    context.push(ContextLine {
+                        line: after,
+                        text: String::from_utf8_lossy(split_line_ending(&raw).0).into_owned(),
+                    });
+                }
+
+                break Some(Mismatch {
+                    kind,
+                    line,
+                    expected: (kind != MismatchKind::ExtraLine).then(|| expected.into_owned()),
+                    actual: Some(text),
+                    context,
+                });
+            }
+        }
+    };
+
+    Ok(VerifyReport {
+        lines: line,
+        mismatch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(n: u32) -> Vec<u8> {
+        let mut out = Vec::new();
+        crate::write_fizzbuzz_to(&mut out, n).unwrap();
+        out
+    }
+
+    fn check(input: &[u8], n: Option<u64>, strictness: Strictness) -> VerifyReport {
+        let options = VerifyOptions {
+            n,
+            strictness,
+            context: 2,
+        };
+        verify(input, &FizzBuzzRules::classic(), &options).unwrap()
+    }
+
+    fn mismatch(input: &[u8], n: Option<u64>, strictness: Strictness) -> Mismatch {
+        check(input, n, strictness)
+            .mismatch
+            .expect("debería fallar")
+    }
+
+    #[test]
+    fn accepts_fizzbuzz_output() {
+        let report = check(&output(100), Some(100), Strictness::Strict);
+        assert!(report.is_ok());
+        assert_eq!(report.lines, 100);
+        assert!(check(&output(0), Some(0), Strictness::Strict).is_ok());
+        assert!(check(b"1\r\n2\r\nfizz", Some(3), Strictness::Lenient).is_ok());
+    }
+
+    #[test]
+    fn wrong_token() {
+        let mut input = output(20);
+        // La línea 9 ("fizz") pasa a ser "9".
+        let at = crate::byte_offset_of_line(9) as usize;
+        input.splice(at..at + 4, *b"9");
+
+        let mismatch = mismatch(&input, None, Strictness::Normal);
+        assert_eq!(mismatch.kind, MismatchKind::WrongToken);
+        assert_eq!(mismatch.line, 9);
+        assert_eq!(mismatch.expected.as_deref(), Some("fizz"));
+        assert_eq!(mismatch.actual.as_deref(), Some("9"));
+        let lines: Vec<u64> = mismatch.context.iter().map(|c| c.line).collect();
+        assert_eq!(lines, [7, 8, 9, 10, 11]);
+        assert_eq!(mismatch.context[0].text, "7");
+    }
+
+    #[test]
+    fn missing_and_extra_lines() {
+        let mismatch = mismatch(&output(10), Some(12), Strictness::Normal);
+        assert_eq!(mismatch.kind, MismatchKind::MissingLine);
+        assert_eq!(mismatch.line, 11);
+        assert_eq!(mismatch.expected.as_deref(), Some("11"));
+        assert_eq!(mismatch.actual, None);
+
+        let mismatch = self::mismatch(&output(10), Some(8), Strictness::Normal);
+        assert_eq!(mismatch.kind, MismatchKind::ExtraLine);
+        assert_eq!(mismatch.line, 9);
+        assert_eq!(mismatch.expected, None);
+        assert_eq!(mismatch.actual.as_deref(), Some("fizz"));
+    }
+
+    #[test]
+    fn line_endings() {
+        let mismatch = mismatch(b"1\n2\r\nfizz\n", None, Strictness::Normal);
+        assert_eq!(
+            (mismatch.kind, mismatch.line),
+            (MismatchKind::CarriageReturn, 2)
+        );
+
+        let mismatch = self::mismatch(b"1\n2\nfizz", None, Strictness::Strict);
+        assert_eq!(
+            (mismatch.kind, mismatch.line),
+            (MismatchKind::MissingFinalNewline, 3)
+        );
+        assert!(check(b"1\n2\nfizz", None, Strictness::Normal).is_ok());
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mismatch = mismatch(b"1\n\xff\xfe\nfizz\n", None, Strictness::Lenient);
+        assert_eq!(
+            (mismatch.kind, mismatch.line),
+            (MismatchKind::InvalidUtf8, 2)
+        );
+    }
+}